@@ -1,6 +1,7 @@
 use aoc_2024::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11};
 
 use criterion::{criterion_group, criterion_main, Criterion};
+use gridthings::{Cell, Direction, Grid, Point};
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("day01 part1", |b| b.iter(|| day01::part1("data/01.txt")));
@@ -27,5 +28,85 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("day11 part2", |b| b.iter(|| day11::part2("data/day11.txt")));
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn pathfinding_benchmark(c: &mut Criterion) {
+    // Route from the day06 guard to the top-left corner around the obstacles
+    let content = std::fs::read_to_string("data/06.txt").unwrap();
+    let grid: Grid<char> = Grid::from_str(&content).unwrap();
+    let start = grid.iter_cells().find(|cell| cell.value == '^').unwrap();
+    let cost = |_: &Cell<char>, to: &Cell<char>| (to.value != '#').then_some(1);
+    let dijkstra = || grid.dijkstra(start.y, start.x, Direction::cardinal(), cost);
+    let astar = || {
+        grid.astar(
+            start.y,
+            start.x,
+            &[(0, 0)],
+            Direction::cardinal(),
+            cost,
+            Point::manhattan,
+        )
+    };
+
+    // How much of the grid each search expands, reported once outside the timed runs
+    println!(
+        "explored cells to (0, 0): dijkstra {}, astar {}",
+        dijkstra().explored,
+        astar().explored
+    );
+    c.bench_function("gridthings dijkstra", |b| b.iter(dijkstra));
+    c.bench_function("gridthings astar", |b| b.iter(astar));
+}
+
+fn neighbors_benchmark(c: &mut Criterion) {
+    // Allocating Vec APIs against the borrowing iterators on the day10 grid
+    let content = std::fs::read_to_string("data/10.txt").unwrap();
+    let grid: Grid<u32> = Grid::from_str(&content).unwrap();
+
+    c.bench_function("gridthings get_cell_neighbors", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for cell in grid.iter_cells() {
+                for neighbor in grid.get_cell_neighbors(cell.y, cell.x, Direction::cardinal()) {
+                    total += neighbor.value;
+                }
+            }
+            total
+        })
+    });
+    c.bench_function("gridthings neighbors", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for cell in grid.iter_cells() {
+                for neighbor in grid.neighbors(cell.y, cell.x, &Direction::CARDINAL) {
+                    total += neighbor.value;
+                }
+            }
+            total
+        })
+    });
+    c.bench_function("gridthings iter_rows", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for row in grid.iter_rows() {
+                total += row.iter().map(|cell| cell.value).sum::<u32>();
+            }
+            total
+        })
+    });
+    c.bench_function("gridthings row", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for y in 0..grid.col_len as i32 {
+                total += grid.row(y).map(|cell| cell.value).sum::<u32>();
+            }
+            total
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    pathfinding_benchmark,
+    neighbors_benchmark
+);
 criterion_main!(benches);
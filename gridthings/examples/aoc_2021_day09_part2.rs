@@ -0,0 +1,50 @@
+// https://adventofcode.com/2021/day/9#part2
+
+use gridthings::{Direction, Grid};
+use std::time::Instant;
+
+const INPUT: &str = r#"
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+"#;
+
+fn solve() -> usize {
+    let grid: Grid<u32> = Grid::from_str(INPUT).unwrap();
+    println!(
+        "Parsed grid with {} rows and {} columns",
+        grid.row_len, grid.col_len
+    );
+
+    // Basins are connected areas of anything that isn't a 9
+    let result = grid.regions(Direction::cardinal(), |a, b| a.value != 9 && b.value != 9);
+    let mut basin_sizes: Vec<usize> = result
+        .regions
+        .iter()
+        .filter(|region| region.cells[0].value != 9)
+        .map(|region| region.area())
+        .collect();
+    println!("Found {} basins", basin_sizes.len());
+
+    basin_sizes.sort_unstable_by(|a, b| b.cmp(a));
+    basin_sizes.iter().take(3).product()
+}
+
+fn main() {
+    let start_time = Instant::now();
+    let answer = solve();
+    println!("Product of three largest basins: {}", answer);
+    println!("Time taken: {:?}", start_time.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(), 1134);
+    }
+}
@@ -39,7 +39,7 @@ fn solve() -> u32 {
     for row in grid.iter_rows() {
         let mut curr_cells = Vec::new();
         for cell in row {
-            if cell.value.is_digit(10) {
+            if cell.value.is_ascii_digit() {
                 curr_cells.push(cell);
             } else {
                 if curr_cells.len() > 1 {
@@ -76,7 +76,7 @@ fn solve() -> u32 {
         let mut is_adjacent = false;
         for cell in continguous_number.cells {
             for neighbor in grid.get_cell_neighbors(cell.y, cell.x, Direction::all()) {
-                if !neighbor.value.is_digit(10) && neighbor.value != '.' {
+                if !neighbor.value.is_ascii_digit() && neighbor.value != '.' {
                     is_adjacent = true;
                 }
             }
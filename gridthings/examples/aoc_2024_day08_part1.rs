@@ -74,8 +74,8 @@ fn solve() -> u32 {
         for (i, cell1) in cells.iter().enumerate() {
             for cell2 in cells.iter().skip(i + 1) {
                 // Calculate vector between cells
-                let y_diff = cell2.y as i32 - cell1.y as i32;
-                let x_diff = cell2.x as i32 - cell1.x as i32;
+                let y_diff = cell2.y - cell1.y;
+                let x_diff = cell2.x - cell1.x;
 
                 // Calculate antinode positions (one in each direction from the cells)
                 let antinode1_y = cell1.y - y_diff;
@@ -1,7 +1,7 @@
 // https://projecteuler.net/problem=11
 use std::time::Instant;
 
-use gridthings::Grid;
+use gridthings::{Direction, Grid, Separator};
 
 const INPUT: &str = r#"
 08 02 22 97 38 15 00 40 00 75 04 05 07 78 52 12 50 77 91 08
@@ -27,32 +27,31 @@ const INPUT: &str = r#"
 "#;
 
 fn solve() -> i32 {
-    let data = INPUT
-        .trim()
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse::<i32>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    let grid = Grid::from_vecs(&data);
+    let grid: Grid<i32> = Grid::try_from_tokens(INPUT, Separator::Whitespace).unwrap();
     println!(
         "Parsed grid with {} rows and {} columns",
         grid.row_len, grid.col_len
     );
 
+    // Products are the same read in either direction, so four directions cover every line
+    let directions = [
+        Direction::Right,
+        Direction::Down,
+        Direction::DiagonalDownRight,
+        Direction::DiagonalDownLeft,
+    ];
     let mut max_product = 0;
-    for cell in grid.iter_cells() {
-        for segment in grid.all_segments(cell.y, cell.x, 4) {
-            let product = segment.values.iter().product();
-            if product > max_product {
-                println!(
-                    "Found new max product from cell {:?}. product: {}",
-                    cell, product
-                );
-                max_product = product;
+    for direction in directions {
+        for line in grid.lines_in_direction(direction) {
+            for (i, window) in line.values.windows(4).enumerate() {
+                let product = window.iter().product();
+                if product > max_product {
+                    println!(
+                        "Found new max product from cell {:?}. product: {}",
+                        line.cells[i], product
+                    );
+                    max_product = product;
+                }
             }
         }
     }
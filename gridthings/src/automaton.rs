@@ -0,0 +1,352 @@
+use crate::{direction::DirectionSet, grid::Grid};
+use std::{collections::HashMap, hash::Hash};
+
+// The neighbor values an automaton rule sees for one cell. Neighbors past the edge are
+// left out, unless the grid is wrapping in which case they come from the opposite edge
+pub struct Neighbors<'a, T> {
+    values: [Option<&'a T>; 8],
+    len: usize,
+}
+
+impl<'a, T> Neighbors<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.values[..self.len].iter().flatten().copied()
+    }
+
+    pub fn count_where<F: Fn(&T) -> bool>(&self, predicate: F) -> usize {
+        self.iter().filter(|value| predicate(value)).count()
+    }
+
+    // Number of neighbors equal to value, e.g. live cells in the game of life
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.count_where(|neighbor| neighbor == value)
+    }
+}
+
+// Why Grid::run_automaton stopped. Generation 0 is the grid it started from
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AutomatonEnd {
+    // This generation maps to itself, the grid will never change again
+    Stable { generation: usize },
+    // Generation start + period is the same as generation start
+    Cycle { start: usize, period: usize },
+    // max_generations were run without the grid settling
+    Limit { generation: usize },
+}
+
+impl<T: Clone> Grid<T> {
+    fn automaton_value<F>(&self, idx: usize, deltas: &[(i32, i32)], rule: &F) -> T
+    where
+        F: Fn(&T, &Neighbors<T>) -> T,
+    {
+        let y = (idx / self.row_len) as i32;
+        let x = (idx % self.row_len) as i32;
+        let mut neighbors = Neighbors {
+            values: [None; 8],
+            len: 0,
+        };
+        for (y_step, x_step) in deltas {
+            if let Some(value) = self.get(y + y_step, x + x_step) {
+                neighbors.values[neighbors.len] = Some(value);
+                neighbors.len += 1;
+            }
+        }
+        rule(&self.data[idx], &neighbors)
+    }
+
+    // step_automaton without allocating. The next generation is built in buffer and swapped
+    // in, leaving the old generation in buffer to be reused by the next call
+    pub fn step_automaton_into<D, F>(&mut self, buffer: &mut Vec<T>, directions: D, rule: F) -> bool
+    where
+        T: PartialEq,
+        D: Into<DirectionSet>,
+        F: Fn(&T, &Neighbors<T>) -> T,
+    {
+        let deltas: Vec<(i32, i32)> = directions.into().iter().map(|d| d.delta()).collect();
+        buffer.clear();
+        buffer.extend((0..self.data.len()).map(|idx| self.automaton_value(idx, &deltas, &rule)));
+        std::mem::swap(&mut self.data, buffer);
+        self.data != *buffer
+    }
+
+    // Replace every cell with rule(value, neighbors) at once, looking at neighbors in the
+    // given directions. True if any cell changed
+    pub fn step_automaton<D, F>(&mut self, directions: D, rule: F) -> bool
+    where
+        T: PartialEq,
+        D: Into<DirectionSet>,
+        F: Fn(&T, &Neighbors<T>) -> T,
+    {
+        let mut buffer = Vec::with_capacity(self.data.len());
+        self.step_automaton_into(&mut buffer, directions, rule)
+    }
+
+    // Step until a generation repeats or max_generations have run. The grid is left at the
+    // last generation computed, which for a cycle is generation start + period
+    pub fn run_automaton<D, F>(
+        &mut self,
+        directions: D,
+        rule: F,
+        max_generations: usize,
+    ) -> AutomatonEnd
+    where
+        T: Hash + Eq,
+        D: Into<DirectionSet>,
+        F: Fn(&T, &Neighbors<T>) -> T,
+    {
+        let directions = directions.into();
+        self.run_until_repeat(max_generations, |grid, buffer| {
+            grid.step_automaton_into(buffer, directions, &rule)
+        })
+    }
+
+    // The loop behind run_automaton and par_run_automaton, step advances the grid by one
+    // generation using the buffer and returns whether anything changed
+    fn run_until_repeat<S>(&mut self, max_generations: usize, mut step: S) -> AutomatonEnd
+    where
+        T: Hash + Eq,
+        S: FnMut(&mut Self, &mut Vec<T>) -> bool,
+    {
+        let mut buffer = Vec::with_capacity(self.data.len());
+        let mut seen: HashMap<Vec<T>, usize> = HashMap::from([(self.data.clone(), 0)]);
+        for generation in 1..=max_generations {
+            if !step(self, &mut buffer) {
+                return AutomatonEnd::Stable {
+                    generation: generation - 1,
+                };
+            }
+            if let Some(&start) = seen.get(&self.data) {
+                return AutomatonEnd::Cycle {
+                    start,
+                    period: generation - start,
+                };
+            }
+            seen.insert(self.data.clone(), generation);
+        }
+        AutomatonEnd::Limit {
+            generation: max_generations,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Clone + PartialEq + Send + Sync> Grid<T> {
+    // step_automaton with the cells computed in parallel on the rayon thread pool
+    pub fn par_step_automaton<D, F>(&mut self, directions: D, rule: F) -> bool
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&T, &Neighbors<T>) -> T + Sync,
+    {
+        let mut buffer = Vec::with_capacity(self.data.len());
+        self.par_step_automaton_into(&mut buffer, directions, rule)
+    }
+
+    // step_automaton_into with the cells computed in parallel
+    pub fn par_step_automaton_into<D, F>(
+        &mut self,
+        buffer: &mut Vec<T>,
+        directions: D,
+        rule: F,
+    ) -> bool
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&T, &Neighbors<T>) -> T + Sync,
+    {
+        use rayon::prelude::*;
+
+        let deltas: Vec<(i32, i32)> = directions.into().iter().map(|d| d.delta()).collect();
+        buffer.clear();
+        buffer.par_extend(
+            (0..self.data.len())
+                .into_par_iter()
+                .map(|idx| self.automaton_value(idx, &deltas, &rule)),
+        );
+        std::mem::swap(&mut self.data, buffer);
+        self.data != *buffer
+    }
+
+    // run_automaton with each generation computed in parallel
+    pub fn par_run_automaton<D, F>(
+        &mut self,
+        directions: D,
+        rule: F,
+        max_generations: usize,
+    ) -> AutomatonEnd
+    where
+        T: Hash + Eq,
+        D: Into<DirectionSet>,
+        F: Fn(&T, &Neighbors<T>) -> T + Sync,
+    {
+        let directions = directions.into();
+        self.run_until_repeat(max_generations, |grid, buffer| {
+            grid.par_step_automaton_into(buffer, directions, &rule)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+
+    fn life(alive: &char, neighbors: &Neighbors<char>) -> char {
+        match (alive, neighbors.count(&'#')) {
+            ('#', 2 | 3) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    fn count_alive(grid: &Grid<char>) -> usize {
+        grid.data.iter().filter(|&&c| c == '#').count()
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut counts = Grid::from_vecs(&[vec![1, 0, 1], vec![0, 1, 0], vec![1, 1, 0]]);
+        let mut lens = counts.clone();
+        counts.step_automaton(DirectionSet::ALL, |_, neighbors| neighbors.count(&1) as i32);
+        assert_eq!(counts.data, vec![1, 3, 1, 4, 4, 3, 2, 2, 2]);
+
+        lens.step_automaton(DirectionSet::ALL, |_, neighbors| neighbors.len() as i32);
+        assert_eq!(lens.data, vec![3, 5, 3, 5, 8, 5, 3, 5, 3]);
+        let mut wrapping = lens.with_wrapping(true);
+        wrapping.step_automaton(DirectionSet::CARDINAL, |_, neighbors| {
+            neighbors.len() as i32
+        });
+        assert!(wrapping.data.iter().all(|&len| len == 4));
+    }
+
+    #[test]
+    fn test_game_of_life_steps() {
+        // Advent of Code 2015 day 18
+        let mut grid: Grid<char> =
+            Grid::from_str(".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..").unwrap();
+        let counts: Vec<usize> = (0..4)
+            .map(|_| {
+                grid.step_automaton(DirectionSet::ALL, life);
+                count_alive(&grid)
+            })
+            .collect();
+        assert_eq!(counts, vec![11, 8, 4, 4]);
+        assert!(!grid.step_automaton(DirectionSet::ALL, life));
+    }
+
+    #[test]
+    fn test_step_into_reuses_buffer() {
+        let mut grid: Grid<char> = Grid::from_str(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let start = grid.data.clone();
+        let mut buffer = Vec::new();
+        assert!(grid.step_automaton_into(&mut buffer, DirectionSet::ALL, life));
+        // The blinker's previous generation is left in the buffer
+        assert_eq!(buffer, start);
+        let horizontal = grid.data.clone();
+        assert!(grid.step_automaton_into(&mut buffer, DirectionSet::ALL, life));
+        assert_eq!(grid.data, start);
+        assert_eq!(buffer, horizontal);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let mut grid: Grid<char> = Grid::from_str("....\n.##.\n.#..\n....").unwrap();
+        let end = grid.run_automaton(DirectionSet::ALL, life, 100);
+        assert_eq!(end, AutomatonEnd::Stable { generation: 1 });
+        assert_eq!(grid.to_string(), "....\n.##.\n.##.\n....");
+    }
+
+    #[test]
+    fn test_run_until_cycle() {
+        let mut blinker: Grid<char> = Grid::from_str(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let end = blinker.run_automaton(DirectionSet::ALL, life, 100);
+        assert_eq!(
+            end,
+            AutomatonEnd::Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+
+        // A glider on a 5x5 torus moves one cell diagonally every 4 generations
+        let mut glider: Grid<char> = Grid::from_str(".#...\n..#..\n###..\n.....\n.....")
+            .unwrap()
+            .with_wrapping(true);
+        let end = glider.run_automaton(DirectionSet::ALL, life, 100);
+        assert_eq!(
+            end,
+            AutomatonEnd::Cycle {
+                start: 0,
+                period: 20
+            }
+        );
+        assert_eq!(count_alive(&glider), 5);
+
+        let mut glider = Grid::from_str(".#...\n..#..\n###..\n.....\n.....")
+            .unwrap()
+            .with_wrapping(true);
+        let end = glider.run_automaton(DirectionSet::ALL, life, 10);
+        assert_eq!(end, AutomatonEnd::Limit { generation: 10 });
+    }
+
+    #[test]
+    fn test_fixed_boundary() {
+        // Without wrapping the glider hits the corner and settles into a block
+        let mut glider: Grid<char> = Grid::from_str(".#...\n..#..\n###..\n.....\n.....").unwrap();
+        let end = glider.run_automaton(DirectionSet::ALL, life, 100);
+        assert!(matches!(end, AutomatonEnd::Stable { .. }));
+        assert_eq!(count_alive(&glider), 4);
+    }
+
+    #[test]
+    fn test_cardinal_rule() {
+        // Spread to every cell next to a marked one, diagonals do not count
+        let mut grid = Grid::from_vecs(&[vec![0, 0, 0], vec![0, 1, 0], vec![0, 0, 0]]);
+        grid.step_automaton(Direction::cardinal(), |&value, neighbors| {
+            value.max(neighbors.count(&1).min(1) as i32)
+        });
+        assert_eq!(grid.data, vec![0, 1, 0, 1, 1, 1, 0, 1, 0]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_step_automaton() {
+        let input = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+        let mut sequential: Grid<char> = Grid::from_str(input).unwrap();
+        let mut parallel = sequential.clone();
+        for _ in 0..4 {
+            let changed = sequential.step_automaton(DirectionSet::ALL, life);
+            assert_eq!(
+                parallel.par_step_automaton(DirectionSet::ALL, life),
+                changed
+            );
+            assert_eq!(parallel.data, sequential.data);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_run_automaton() {
+        let input = ".#...\n..#..\n###..\n.....\n.....";
+        let mut sequential: Grid<char> = Grid::from_str(input).unwrap().with_wrapping(true);
+        let mut parallel = sequential.clone();
+        let end = sequential.run_automaton(DirectionSet::ALL, life, 100);
+        assert_eq!(
+            parallel.par_run_automaton(DirectionSet::ALL, life, 100),
+            end
+        );
+        assert_eq!(parallel.data, sequential.data);
+
+        let mut buffer = Vec::new();
+        let mut still: Grid<char> = Grid::from_str("....\n.##.\n.##.\n....").unwrap();
+        assert!(!still.par_step_automaton_into(&mut buffer, DirectionSet::ALL, life));
+    }
+}
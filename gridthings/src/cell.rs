@@ -1,12 +1,25 @@
+use crate::point::Point;
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-pub struct Cell<T: Copy> {
+pub struct Cell<T: Clone> {
     pub value: T,
     pub y: i32,
     pub x: i32,
 }
 
-impl<T: Copy> Cell<T> {
+impl<T: Clone> Cell<T> {
     pub fn new(value: T, y: i32, x: i32) -> Self {
         Self { value, y, x }
     }
+
+    pub fn point(&self) -> Point {
+        Point::new(self.y, self.x)
+    }
+}
+
+impl<T: Clone> Cell<&T> {
+    // Turn a borrowed cell into one that owns a clone of its value
+    pub fn cloned(&self) -> Cell<T> {
+        Cell::new(self.value.clone(), self.y, self.x)
+    }
 }
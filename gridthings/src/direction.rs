@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+use std::{fmt::Display, ops::BitOr, str::FromStr};
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Direction {
     Left,
     Right,
@@ -11,6 +13,24 @@ pub enum Direction {
 }
 
 impl Direction {
+    // Same as all() and cardinal(), as arrays that can be borrowed without allocating
+    pub const ALL: [Direction; 8] = [
+        Direction::Right,
+        Direction::DiagonalDownRight,
+        Direction::Down,
+        Direction::DiagonalDownLeft,
+        Direction::Left,
+        Direction::DiagonalUpLeft,
+        Direction::Up,
+        Direction::DiagonalUpRight,
+    ];
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+    ];
+
     // Order is clockwise starting from right
     pub fn all() -> Vec<Direction> {
         vec![
@@ -46,4 +66,373 @@ impl Direction {
             Direction::DiagonalDownLeft => (1, -1),
         }
     }
+
+    // Position in Direction::all(), clockwise from Right
+    pub fn index(&self) -> usize {
+        match self {
+            Direction::Right => 0,
+            Direction::DiagonalDownRight => 1,
+            Direction::Down => 2,
+            Direction::DiagonalDownLeft => 3,
+            Direction::Left => 4,
+            Direction::DiagonalUpLeft => 5,
+            Direction::Up => 6,
+            Direction::DiagonalUpRight => 7,
+        }
+    }
+
+    // Inverse of index(), wraps around so any usize is valid
+    pub fn from_index(index: usize) -> Direction {
+        match index % 8 {
+            0 => Direction::Right,
+            1 => Direction::DiagonalDownRight,
+            2 => Direction::Down,
+            3 => Direction::DiagonalDownLeft,
+            4 => Direction::Left,
+            5 => Direction::DiagonalUpLeft,
+            6 => Direction::Up,
+            _ => Direction::DiagonalUpRight,
+        }
+    }
+
+    // Degrees clockwise from Right, y grows downward so Down is 90
+    pub fn angle(&self) -> u32 {
+        self.index() as u32 * 45
+    }
+
+    // Rotate 90 degrees clockwise, diagonals stay diagonal
+    pub fn turn_right(&self) -> Direction {
+        Direction::from_index(self.index() + 2)
+    }
+
+    // Rotate 90 degrees counter-clockwise, diagonals stay diagonal
+    pub fn turn_left(&self) -> Direction {
+        Direction::from_index(self.index() + 6)
+    }
+
+    pub fn turn_right_45(&self) -> Direction {
+        Direction::from_index(self.index() + 1)
+    }
+
+    pub fn turn_left_45(&self) -> Direction {
+        Direction::from_index(self.index() + 7)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        Direction::from_index(self.index() + 4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    // Parse the single character glyphs used by puzzles: ^>v< arrows, U/D/L/R and N/E/S/W
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' | 'U' | 'u' | 'N' | 'n' => Some(Direction::Up),
+            '>' | 'R' | 'r' | 'E' | 'e' => Some(Direction::Right),
+            'v' | 'D' | 'd' | 'S' | 's' => Some(Direction::Down),
+            '<' | 'L' | 'l' | 'W' | 'w' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    // ^>v< for cardinal directions, arrows for diagonals
+    pub fn arrow(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::DiagonalUpRight => '↗',
+            Direction::DiagonalDownRight => '↘',
+            Direction::DiagonalDownLeft => '↙',
+            Direction::DiagonalUpLeft => '↖',
+        }
+    }
+
+    // U/D/L/R, diagonals have no letter
+    pub fn letter(&self) -> Option<char> {
+        match self {
+            Direction::Up => Some('U'),
+            Direction::Right => Some('R'),
+            Direction::Down => Some('D'),
+            Direction::Left => Some('L'),
+            _ => None,
+        }
+    }
+
+    pub fn compass(&self) -> &'static str {
+        match self {
+            Direction::Up => "N",
+            Direction::DiagonalUpRight => "NE",
+            Direction::Right => "E",
+            Direction::DiagonalDownRight => "SE",
+            Direction::Down => "S",
+            Direction::DiagonalDownLeft => "SW",
+            Direction::Left => "W",
+            Direction::DiagonalUpLeft => "NW",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DirectionParseError {
+    pub value: String,
+}
+
+impl std::fmt::Display for DirectionParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse direction: '{}'", self.value)
+    }
+}
+
+impl std::error::Error for DirectionParseError {}
+
+// Accepts anything from_char does plus two letter compass points like "NE" and the
+// diagonal arrows, so every Display output parses back
+impl FromStr for Direction {
+    type Err = DirectionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(direction) = Direction::from_char(c) {
+                return Ok(direction);
+            }
+        }
+        Direction::all()
+            .into_iter()
+            .find(|d| d.compass().eq_ignore_ascii_case(s) || s.chars().eq([d.arrow()]))
+            .ok_or_else(|| DirectionParseError {
+                value: s.to_string(),
+            })
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+// A set of Directions packed into one byte, bit n is Direction::from_index(n).
+// It is Copy so it can be passed around without the Vec allocations of Direction::all()
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+pub struct DirectionSet {
+    bits: u8,
+}
+
+impl DirectionSet {
+    pub const EMPTY: DirectionSet = DirectionSet { bits: 0 };
+    pub const CARDINAL: DirectionSet = DirectionSet { bits: 0b0101_0101 };
+    pub const DIAGONAL: DirectionSet = DirectionSet { bits: 0b1010_1010 };
+    pub const ALL: DirectionSet = DirectionSet { bits: 0b1111_1111 };
+
+    pub fn contains(&self, direction: &Direction) -> bool {
+        self.bits & (1 << direction.index()) != 0
+    }
+
+    pub fn insert(&mut self, direction: &Direction) {
+        self.bits |= 1 << direction.index();
+    }
+
+    pub fn remove(&mut self, direction: &Direction) {
+        self.bits &= !(1 << direction.index());
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    // Same clockwise-from-Right order as Direction::all()
+    pub fn iter(&self) -> impl Iterator<Item = Direction> {
+        let bits = self.bits;
+        (0..8)
+            .filter(move |i| bits & (1 << i) != 0)
+            .map(Direction::from_index)
+    }
+}
+
+impl BitOr for DirectionSet {
+    type Output = DirectionSet;
+
+    fn bitor(self, other: DirectionSet) -> DirectionSet {
+        DirectionSet {
+            bits: self.bits | other.bits,
+        }
+    }
+}
+
+impl From<Direction> for DirectionSet {
+    fn from(direction: Direction) -> Self {
+        let mut set = DirectionSet::EMPTY;
+        set.insert(&direction);
+        set
+    }
+}
+
+impl From<&[Direction]> for DirectionSet {
+    fn from(directions: &[Direction]) -> Self {
+        let mut set = DirectionSet::EMPTY;
+        for direction in directions {
+            set.insert(direction);
+        }
+        set
+    }
+}
+
+impl From<Vec<Direction>> for DirectionSet {
+    fn from(directions: Vec<Direction>) -> Self {
+        DirectionSet::from(directions.as_slice())
+    }
+}
+
+impl<const N: usize> From<[Direction; N]> for DirectionSet {
+    fn from(directions: [Direction; N]) -> Self {
+        DirectionSet::from(directions.as_slice())
+    }
+}
+
+// Directions for get_cell_neighbors. Lists keep their order and any repeats, a DirectionSet
+// goes in its bit order
+pub trait IntoDirections {
+    fn into_directions(self) -> impl Iterator<Item = Direction>;
+}
+
+impl IntoDirections for DirectionSet {
+    fn into_directions(self) -> impl Iterator<Item = Direction> {
+        self.iter()
+    }
+}
+
+impl IntoDirections for Direction {
+    fn into_directions(self) -> impl Iterator<Item = Direction> {
+        std::iter::once(self)
+    }
+}
+
+impl IntoDirections for Vec<Direction> {
+    fn into_directions(self) -> impl Iterator<Item = Direction> {
+        self.into_iter()
+    }
+}
+
+impl IntoDirections for &[Direction] {
+    fn into_directions(self) -> impl Iterator<Item = Direction> {
+        self.iter().cloned()
+    }
+}
+
+impl<const N: usize> IntoDirections for [Direction; N] {
+    fn into_directions(self) -> impl Iterator<Item = Direction> {
+        self.into_iter()
+    }
+}
+
+impl<const N: usize> IntoDirections for &[Direction; N] {
+    fn into_directions(self) -> impl Iterator<Item = Direction> {
+        self.iter().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consts_match_vecs() {
+        assert_eq!(Direction::ALL.to_vec(), Direction::all());
+        assert_eq!(Direction::CARDINAL.to_vec(), Direction::cardinal());
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        for (i, direction) in Direction::all().into_iter().enumerate() {
+            assert_eq!(direction.index(), i);
+            assert_eq!(Direction::from_index(i), direction);
+        }
+        assert_eq!(Direction::Down.angle(), 90);
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Up.turn_right_45(), Direction::DiagonalUpRight);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::DiagonalUpLeft);
+        assert_eq!(
+            Direction::DiagonalUpRight.turn_right(),
+            Direction::DiagonalDownRight
+        );
+        for direction in Direction::all() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.opposite().opposite(), direction);
+            let (y, x) = direction.delta();
+            assert_eq!(direction.opposite().delta(), (-y, -x));
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Direction::from_char('^'), Some(Direction::Up));
+        assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('L'), Some(Direction::Left));
+        assert_eq!(Direction::from_char('E'), Some(Direction::Right));
+        assert_eq!(Direction::from_char('#'), None);
+        assert_eq!(
+            "SW".parse::<Direction>().unwrap(),
+            Direction::DiagonalDownLeft
+        );
+        assert_eq!(
+            "ne".parse::<Direction>().unwrap(),
+            Direction::DiagonalUpRight
+        );
+        assert!("up".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_print_round_trip() {
+        for direction in Direction::all() {
+            assert_eq!(
+                direction.to_string().parse::<Direction>().unwrap(),
+                direction
+            );
+            assert_eq!(direction.compass().parse::<Direction>().unwrap(), direction);
+        }
+        assert_eq!(Direction::Left.to_string(), "<");
+        assert_eq!(Direction::Down.letter(), Some('D'));
+    }
+
+    #[test]
+    fn test_direction_set() {
+        assert_eq!(
+            DirectionSet::CARDINAL.iter().collect::<Vec<_>>(),
+            Direction::cardinal()
+        );
+        assert_eq!(
+            DirectionSet::ALL.iter().collect::<Vec<_>>(),
+            Direction::all()
+        );
+        assert_eq!(
+            DirectionSet::CARDINAL | DirectionSet::DIAGONAL,
+            DirectionSet::ALL
+        );
+        assert_eq!(
+            DirectionSet::from(Direction::cardinal()),
+            DirectionSet::CARDINAL
+        );
+
+        let mut set = DirectionSet::from([Direction::Up, Direction::Left]);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Direction::Up));
+        set.remove(&Direction::Up);
+        assert!(!set.contains(&Direction::Up));
+        set.remove(&Direction::Left);
+        assert!(set.is_empty());
+    }
 }
@@ -1,11 +1,23 @@
-use crate::{cell::Cell, direction::Direction, segment::Segment};
-use std::str::FromStr;
+use crate::{
+    cell::Cell,
+    direction::{Direction, IntoDirections},
+    parse::content_lines,
+    point::Point,
+    segment::Segment,
+};
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
 
-#[derive(Clone, Debug)]
-pub struct Grid<T: Copy> {
+// When wrapping is set the grid is a torus, coordinates past an edge continue on the
+// opposite edge instead of being out of bounds
+#[derive(Clone)]
+pub struct Grid<T: Clone> {
     pub data: Vec<T>,
     pub row_len: usize,
     pub col_len: usize,
+    pub wrapping: bool,
 }
 
 #[derive(Debug)]
@@ -16,78 +28,301 @@ pub struct GridParseError {
     pub target_type: String,
 }
 
+// Multi-char tokens from Grid::try_from_tokens are called values instead of characters
 impl std::fmt::Display for GridParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.value.chars().count() {
+            1 => "character",
+            _ => "value",
+        };
         write!(
             f,
-            "Failed to parse grid: character '{}' at position (row {}, col {}) could not be parsed as {}",
-            self.value, self.row, self.col, self.target_type
+            "Failed to parse grid: {} '{}' at position (row {}, col {}) could not be parsed as {}",
+            kind, self.value, self.row, self.col, self.target_type
         )
     }
 }
 
 impl std::error::Error for GridParseError {}
 
-impl<T: Copy + FromStr> Grid<T> {
-    pub fn from_str(input: &str) -> Result<Self, GridParseError> {
+// Everything that can go wrong building a Grid. Rows are counted from 0
+#[derive(Debug)]
+pub enum GridError {
+    // No rows, or rows with no values in them
+    Empty,
+    // Row row has found values where the first row had expected
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    // A cell could not be parsed as the target type
+    Parse(GridParseError),
+    // Separator::FixedWidth(0) can not split a line into tokens
+    ZeroWidth,
+    // More cells than can be addressed or allocated
+    TooLarge,
+}
+
+impl std::fmt::Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "Failed to build grid: there are no values"),
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Failed to build grid: row {} has {} values but the rows before it have {}",
+                row, found, expected
+            ),
+            GridError::Parse(err) => write!(f, "{}", err),
+            GridError::ZeroWidth => write!(
+                f,
+                "Failed to build grid: FixedWidth separator needs a width of at least 1"
+            ),
+            GridError::TooLarge => write!(f, "Failed to build grid: too many cells to store"),
+        }
+    }
+}
+
+impl std::error::Error for GridError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GridError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridParseError> for GridError {
+    fn from(err: GridParseError) -> Self {
+        GridError::Parse(err)
+    }
+}
+
+// One line per row. Single char values are written back to back, so a char or digit grid
+// prints in the same shape Grid::from_str reads. Wider values are right-aligned and space
+// separated like render, so they read back with Separator::Whitespace
+impl<T: Clone + Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self.data.iter().map(|v| v.to_string()).collect();
+        let width = values.iter().map(|v| v.chars().count()).max().unwrap_or(1);
+        let separator = if width > 1 { " " } else { "" };
+        for (y, row) in values.chunks(self.row_len.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, value) in row.iter().enumerate() {
+                if x > 0 {
+                    write!(f, "{}", separator)?;
+                }
+                write!(f, "{:>width$}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// {:?} shows the raw fields, {:#?} lays the values out with row and column indices
+impl<T: Clone + Debug> Debug for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !f.alternate() {
+            return f
+                .debug_struct("Grid")
+                .field("data", &self.data)
+                .field("row_len", &self.row_len)
+                .field("col_len", &self.col_len)
+                .field("wrapping", &self.wrapping)
+                .finish();
+        }
+
+        let values: Vec<String> = self.data.iter().map(|v| format!("{:?}", v)).collect();
+        let value_width = values.iter().map(|v| v.chars().count()).max().unwrap_or(1);
+        let x_width = self.row_len.saturating_sub(1).to_string().len();
+        let width = value_width.max(x_width);
+        let y_width = self.col_len.saturating_sub(1).to_string().len();
+
+        write!(f, "{:y_width$}", "")?;
+        for x in 0..self.row_len {
+            write!(f, " {:>width$}", x)?;
+        }
+        for (y, row) in values.chunks(self.row_len.max(1)).enumerate() {
+            write!(f, "\n{:>y_width$}", y)?;
+            for value in row {
+                write!(f, " {:>width$}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Clone + FromStr> Grid<T> {
+    // One char per cell, spaces included. Blank lines before and after the grid are ignored
+    // and Windows line endings are accepted
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(input: &str) -> Result<Self, GridError> {
         let mut data = Vec::new();
-        for (row_idx, line) in input.trim().lines().enumerate() {
+        for (row_idx, line) in content_lines(input).into_iter().enumerate() {
             let mut row = Vec::new();
             for (col_idx, c) in line.chars().enumerate() {
                 match c.to_string().parse() {
                     Ok(value) => row.push(value),
                     Err(_) => {
-                        return Err(GridParseError {
+                        return Err(GridError::Parse(GridParseError {
                             row: row_idx,
                             col: col_idx,
                             value: c.to_string(),
                             target_type: std::any::type_name::<T>().to_string(),
-                        })
+                        }))
                     }
                 }
             }
             data.push(row);
         }
-        Ok(Self::from_vecs(&data))
+        Self::try_from_rows(data)
+    }
+}
+
+// The row length shared by every row, or the reason there isn't one
+fn check_rows<T>(rows: &[Vec<T>]) -> Result<usize, GridError> {
+    let row_len = rows.first().map_or(0, |row| row.len());
+    if row_len == 0 {
+        return Err(GridError::Empty);
+    }
+    match rows.iter().position(|row| row.len() != row_len) {
+        Some(row) => Err(GridError::RaggedRow {
+            row,
+            expected: row_len,
+            found: rows[row].len(),
+        }),
+        None => Ok(row_len),
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Like from_vecs but takes ownership of the rows so nothing is cloned
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        Self::try_from_rows(rows).unwrap_or_else(|err| panic!("{}", err))
     }
 
+    // Panics if the rows have different lengths or there are none
     pub fn from_vecs(vecs: &[Vec<T>]) -> Self {
-        let data = vecs.iter().flat_map(|vec| vec.iter().cloned()).collect();
-        let row_len = vecs[0].len();
-        let col_len = vecs.len();
-        Self {
-            data,
+        Self::try_from_vecs(vecs).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let row_len = check_rows(&rows)?;
+        let col_len = rows.len();
+        Ok(Self {
+            data: rows.into_iter().flatten().collect(),
             row_len,
             col_len,
-        }
+            wrapping: false,
+        })
     }
 
-    fn is_in_bounds(&self, y: i32, x: i32) -> bool {
+    pub fn try_from_vecs(vecs: &[Vec<T>]) -> Result<Self, GridError> {
+        let row_len = check_rows(vecs)?;
+        Ok(Self {
+            data: vecs.iter().flat_map(|vec| vec.iter().cloned()).collect(),
+            row_len,
+            col_len: vecs.len(),
+            wrapping: false,
+        })
+    }
+
+    pub fn with_wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self
+    }
+
+    pub(crate) fn is_in_bounds(&self, y: i32, x: i32) -> bool {
         y >= 0 && x >= 0 && (y as usize) < self.col_len && (x as usize) < self.row_len
     }
 
-    pub fn get_value(&self, y: i32, x: i32) -> Option<T> {
-        if !self.is_in_bounds(y, x) {
-            return None;
+    // Map (y, x) into the grid, taking both modulo the grid size (negatives included)
+    pub fn wrap_coords(&self, y: i32, x: i32) -> (i32, i32) {
+        if self.row_len == 0 || self.col_len == 0 {
+            return (y, x);
         }
-        Some(self.data[y as usize * self.row_len + x as usize])
+        (
+            y.rem_euclid(self.col_len as i32),
+            x.rem_euclid(self.row_len as i32),
+        )
+    }
+
+    // The in-bounds (y, x) that coordinates refer to, wrapped if the grid is wrapping
+    fn resolve(&self, y: i32, x: i32) -> Option<(i32, i32)> {
+        let (y, x) = match self.wrapping {
+            true => self.wrap_coords(y, x),
+            false => (y, x),
+        };
+        self.is_in_bounds(y, x).then_some((y, x))
+    }
+
+    // Borrow the value at (y, x) without cloning it
+    pub fn get(&self, y: i32, x: i32) -> Option<&T> {
+        let (y, x) = self.resolve(y, x)?;
+        Some(&self.data[y as usize * self.row_len + x as usize])
+    }
+
+    pub fn get_mut(&mut self, y: i32, x: i32) -> Option<&mut T> {
+        let (y, x) = self.resolve(y, x)?;
+        Some(&mut self.data[y as usize * self.row_len + x as usize])
+    }
+
+    // A clone of the value at (y, x), which is a plain copy for Copy types
+    pub fn get_value(&self, y: i32, x: i32) -> Option<T> {
+        self.get(y, x).cloned()
     }
 
     pub fn update_cell_value(&mut self, y: i32, x: i32, value: T) -> Option<()> {
-        if !self.is_in_bounds(y, x) {
-            return None;
-        }
-        self.data[y as usize * self.row_len + x as usize] = value;
+        *self.get_mut(y, x)? = value;
         Some(())
     }
 
+    // In wrapping mode the returned Cell holds the wrapped coordinates
     pub fn get_cell(&self, y: i32, x: i32) -> Option<Cell<T>> {
-        self.get_value(y, x).map(|value| Cell::new(value, y, x))
+        self.get_cell_ref(y, x).map(|cell| cell.cloned())
+    }
+
+    // Like get_cell but the Cell borrows the value instead of holding a clone
+    pub fn get_cell_ref(&self, y: i32, x: i32) -> Option<Cell<&T>> {
+        let (y, x) = self.resolve(y, x)?;
+        Some(Cell::new(
+            &self.data[y as usize * self.row_len + x as usize],
+            y,
+            x,
+        ))
+    }
+
+    // True if point refers to a cell, always true for a non-empty wrapping grid
+    pub fn contains_point(&self, point: Point) -> bool {
+        self.resolve(point.y, point.x).is_some()
+    }
+
+    pub fn get_value_at(&self, point: Point) -> Option<T> {
+        self.get_value(point.y, point.x)
+    }
+
+    pub fn update_cell_value_at(&mut self, point: Point, value: T) -> Option<()> {
+        self.update_cell_value(point.y, point.x, value)
+    }
+
+    pub fn get_cell_at(&self, point: Point) -> Option<Cell<T>> {
+        self.get_cell(point.y, point.x)
     }
 
     pub fn iter_cells(&self) -> impl Iterator<Item = Cell<T>> + '_ {
-        (0..self.col_len as i32).flat_map(move |y| {
-            (0..self.row_len as i32).map(move |x| Cell::new(self.get_value(y, x).unwrap(), y, x))
+        self.cells().map(|cell| cell.cloned())
+    }
+
+    // Borrowing version of iter_cells, in the same row-major order
+    pub fn cells(&self) -> impl Iterator<Item = Cell<&T>> + '_ {
+        self.data.iter().enumerate().map(|(i, value)| {
+            Cell::new(value, (i / self.row_len) as i32, (i % self.row_len) as i32)
         })
     }
 
@@ -96,28 +331,124 @@ impl<T: Copy + FromStr> Grid<T> {
         self.get_cell(y + y_step, x + x_step)
     }
 
-    pub fn get_cell_neighbors(&self, y: i32, x: i32, directions: Vec<Direction>) -> Vec<Cell<T>> {
+    // Neighbors come back in the order of directions, or clockwise from Right for a
+    // DirectionSet
+    pub fn get_cell_neighbors<D: IntoDirections>(
+        &self,
+        y: i32,
+        x: i32,
+        directions: D,
+    ) -> Vec<Cell<T>> {
         directions
-            .iter()
-            .filter_map(|direction| self.get_cell_neighbor(y, x, direction.clone()))
+            .into_directions()
+            .filter_map(|direction| self.get_cell_neighbor(y, x, direction))
             .collect()
     }
 
+    pub fn get_cell_neighbor_at(&self, point: Point, direction: Direction) -> Option<Cell<T>> {
+        self.get_cell_neighbor(point.y, point.x, direction)
+    }
+
+    pub fn get_cell_neighbors_at<D: IntoDirections>(
+        &self,
+        point: Point,
+        directions: D,
+    ) -> Vec<Cell<T>> {
+        self.get_cell_neighbors(point.y, point.x, directions)
+    }
+
+    // Borrowing version of get_cell_neighbors, nothing is allocated. Neighbors come back in
+    // the order of the directions slice, e.g. &Direction::CARDINAL
+    pub fn neighbors<'a>(
+        &'a self,
+        y: i32,
+        x: i32,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Cell<T>> + 'a {
+        directions.iter().filter_map(move |direction| {
+            let (y_step, x_step) = direction.delta();
+            self.get_cell(y + y_step, x + x_step)
+        })
+    }
+
+    // Cells of row y from left to right, empty if y is out of bounds
+    pub fn row(&self, y: i32) -> impl Iterator<Item = Cell<T>> + '_ {
+        let row_len = match self.is_in_bounds(y, 0) {
+            true => self.row_len as i32,
+            false => 0,
+        };
+        (0..row_len).map(move |x| {
+            Cell::new(
+                self.data[y as usize * self.row_len + x as usize].clone(),
+                y,
+                x,
+            )
+        })
+    }
+
+    // Cells of column x from top to bottom, empty if x is out of bounds
+    pub fn column(&self, x: i32) -> impl Iterator<Item = Cell<T>> + '_ {
+        let col_len = match self.is_in_bounds(0, x) {
+            true => self.col_len as i32,
+            false => 0,
+        };
+        (0..col_len).map(move |y| {
+            Cell::new(
+                self.data[y as usize * self.row_len + x as usize].clone(),
+                y,
+                x,
+            )
+        })
+    }
+
     pub fn get_row(&self, y: i32) -> Option<Vec<Cell<T>>> {
         if !self.is_in_bounds(y, 0) {
             return None;
         }
-        Some(
-            (0..self.row_len as i32)
-                .map(|x| self.get_cell(y, x).unwrap())
-                .collect(),
-        )
+        Some(self.row(y).collect())
     }
 
     pub fn iter_rows(&self) -> impl Iterator<Item = Vec<Cell<T>>> + '_ {
         (0..self.col_len as i32).map(move |y| self.get_row(y).unwrap())
     }
 
+    // Every maximal straight line through the grid in the given direction, one Segment per
+    // line ordered by start cell. Lines stop at the grid edge even in wrapping mode
+    pub fn lines_in_direction(
+        &self,
+        direction: Direction,
+    ) -> impl Iterator<Item = Segment<T>> + '_ {
+        let (y_step, x_step) = direction.delta();
+        self.cells()
+            .filter(move |cell| !self.is_in_bounds(cell.y - y_step, cell.x - x_step))
+            .map(move |start| {
+                let mut length = 0;
+                while self.is_in_bounds(
+                    start.y + length as i32 * y_step,
+                    start.x + length as i32 * x_step,
+                ) {
+                    length += 1;
+                }
+                self.get_segment(start.y, start.x, direction.clone(), length)
+                    .unwrap()
+            })
+    }
+
+    // Columns top to bottom, as Segments in the Down direction
+    pub fn iter_cols(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        self.lines_in_direction(Direction::Down)
+    }
+
+    // Diagonals running top-left to bottom-right
+    pub fn iter_diagonals(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        self.lines_in_direction(Direction::DiagonalDownRight)
+    }
+
+    // Diagonals running top-right to bottom-left
+    pub fn iter_anti_diagonals(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        self.lines_in_direction(Direction::DiagonalDownLeft)
+    }
+
     pub fn get_segment(
         &self,
         y_start: i32,
@@ -134,12 +465,21 @@ impl<T: Copy + FromStr> Grid<T> {
             let x = x_start + i as i32 * x_step;
 
             let cell = self.get_cell(y, x)?;
-            values.push(cell.value);
+            values.push(cell.value.clone());
             cells.push(cell);
         }
         Some(Segment::new(values, cells, direction))
     }
 
+    pub fn get_segment_at(
+        &self,
+        start: Point,
+        direction: Direction,
+        length: usize,
+    ) -> Option<Segment<T>> {
+        self.get_segment(start.y, start.x, direction, length)
+    }
+
     pub fn all_segments(&self, y_start: i32, x_start: i32, length: usize) -> Vec<Segment<T>> {
         Direction::all()
             .into_iter()
@@ -147,6 +487,14 @@ impl<T: Copy + FromStr> Grid<T> {
             .collect()
     }
 
+    pub fn all_segments_at(&self, start: Point, length: usize) -> Vec<Segment<T>> {
+        self.all_segments(start.y, start.x, length)
+    }
+
+    pub fn subgrid_at(&self, top_left: Point, height: usize, width: usize) -> Option<Grid<T>> {
+        self.subgrid(top_left.y, top_left.x, height, width)
+    }
+
     pub fn subgrid(&self, y: i32, x: i32, height: usize, width: usize) -> Option<Grid<T>> {
         if !self.is_in_bounds(y, x)
             || !self.is_in_bounds(y + height as i32 - 1, x + width as i32 - 1)
@@ -157,7 +505,7 @@ impl<T: Copy + FromStr> Grid<T> {
         let mut data = Vec::with_capacity(height * width);
         for r in y..(y + height as i32) {
             for c in x..(x + width as i32) {
-                data.push(self.data[r as usize * self.row_len + c as usize]);
+                data.push(self.data[r as usize * self.row_len + c as usize].clone());
             }
         }
 
@@ -165,6 +513,7 @@ impl<T: Copy + FromStr> Grid<T> {
             data,
             row_len: width,
             col_len: height,
+            wrapping: false,
         })
     }
 }
@@ -172,6 +521,7 @@ impl<T: Copy + FromStr> Grid<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{direction::DirectionSet, parse::Separator};
 
     // util make a char grid
     fn make_char_grid() -> Grid<char> {
@@ -222,6 +572,51 @@ mod tests {
             grid.get_cell_neighbors(0, 0, Direction::cardinal()),
             vec![Cell::new(2, 0, 1), Cell::new(4, 1, 0)]
         );
+
+        assert_eq!(
+            grid.get_cell_neighbors(1, 1, DirectionSet::DIAGONAL),
+            vec![
+                Cell::new(9, 2, 2),
+                Cell::new(7, 2, 0),
+                Cell::new(1, 0, 0),
+                Cell::new(3, 0, 2)
+            ]
+        );
+
+        // Lists keep the caller's order and repeats
+        assert_eq!(
+            grid.get_cell_neighbors(1, 1, vec![Direction::Up, Direction::Down]),
+            vec![Cell::new(2, 0, 1), Cell::new(8, 2, 1)]
+        );
+        assert_eq!(
+            grid.get_cell_neighbors(1, 1, &[Direction::Left, Direction::Left]),
+            vec![Cell::new(4, 1, 0), Cell::new(4, 1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_neighbors_iter() {
+        let grid = make_i32_grid();
+        assert_eq!(
+            grid.neighbors(0, 0, &Direction::ALL).collect::<Vec<_>>(),
+            grid.get_cell_neighbors(0, 0, Direction::all())
+        );
+        let values: Vec<i32> = grid
+            .neighbors(1, 1, &[Direction::Up, Direction::Left])
+            .map(|cell| cell.value)
+            .collect();
+        assert_eq!(values, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_row_and_column_iter() {
+        let grid = Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(grid.row(1).collect::<Vec<_>>(), grid.get_row(1).unwrap());
+        let values: Vec<i32> = grid.column(2).map(|cell| cell.value).collect();
+        assert_eq!(values, vec![3, 6]);
+        assert_eq!(grid.column(2).last(), Some(Cell::new(6, 1, 2)));
+        assert_eq!(grid.row(2).count(), 0);
+        assert_eq!(grid.column(-1).count(), 0);
     }
 
     #[test]
@@ -310,6 +705,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_iter_cols() {
+        let grid = Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6]]);
+        let cols: Vec<Vec<i32>> = grid.iter_cols().map(|segment| segment.values).collect();
+        assert_eq!(cols, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert!(grid
+            .iter_cols()
+            .all(|segment| segment.direction == Direction::Down));
+    }
+
+    #[test]
+    fn test_iter_diagonals() {
+        let grid = Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6]]);
+        let diagonals: Vec<Vec<i32>> = grid
+            .iter_diagonals()
+            .map(|segment| segment.values)
+            .collect();
+        assert_eq!(diagonals, vec![vec![1, 5], vec![2, 6], vec![3], vec![4]]);
+
+        let anti_diagonals: Vec<Vec<i32>> = grid
+            .iter_anti_diagonals()
+            .map(|segment| segment.values)
+            .collect();
+        assert_eq!(
+            anti_diagonals,
+            vec![vec![1], vec![2, 4], vec![3, 5], vec![6]]
+        );
+    }
+
+    #[test]
+    fn test_lines_in_direction() {
+        let grid = make_i32_grid();
+        let lines: Vec<Vec<i32>> = grid
+            .lines_in_direction(Direction::Left)
+            .map(|segment| segment.values)
+            .collect();
+        assert_eq!(lines, vec![vec![3, 2, 1], vec![6, 5, 4], vec![9, 8, 7]]);
+
+        // Every cell is covered exactly once in any direction
+        for direction in Direction::all() {
+            let covered: usize = grid.lines_in_direction(direction).map(|s| s.len()).sum();
+            assert_eq!(covered, 9);
+        }
+
+        let wrapping = make_i32_grid().with_wrapping(true);
+        assert_eq!(wrapping.lines_in_direction(Direction::Right).count(), 3);
+    }
+
     #[test]
     fn test_all_segments() {
         let grid = make_i32_grid();
@@ -359,18 +802,147 @@ mod tests {
     #[test]
     fn test_grid_from_str() {
         // Valid char grid
-        let grid: Result<Grid<char>, GridParseError> = Grid::from_str("123\n456\n789");
+        let grid: Result<Grid<char>, GridError> = Grid::from_str("123\n456\n789");
         assert!(grid.is_ok());
 
         // Valid i32 grid
-        let grid: Result<Grid<i32>, GridParseError> = Grid::from_str("123\n456\n789");
+        let grid: Result<Grid<i32>, GridError> = Grid::from_str("123\n456\n789");
         assert!(grid.is_ok());
 
         // Invalid i32 grid (contains non-digit)
-        let grid: Result<Grid<i32>, GridParseError> = Grid::from_str("123\n456\n7x9");
+        let grid: Result<Grid<i32>, GridError> = Grid::from_str("123\n456\n7x9");
         assert!(grid.is_err());
     }
 
+    #[test]
+    fn test_try_from_vecs() {
+        let empty: Vec<Vec<i32>> = Vec::new();
+        assert!(matches!(Grid::try_from_vecs(&empty), Err(GridError::Empty)));
+        assert!(matches!(
+            Grid::<i32>::try_from_vecs(&[vec![], vec![]]),
+            Err(GridError::Empty)
+        ));
+        assert!(matches!(
+            Grid::try_from_vecs(&[vec![1, 2], vec![3, 4], vec![5]]),
+            Err(GridError::RaggedRow {
+                row: 2,
+                expected: 2,
+                found: 1
+            })
+        ));
+        let grid = Grid::try_from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(grid.data, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        // Windows line endings and trailing blank lines
+        let grid: Grid<char> = Grid::from_str("ab\r\ncd\r\n\r\n\n").unwrap();
+        assert_eq!(grid.data, vec!['a', 'b', 'c', 'd']);
+        assert_eq!((grid.row_len, grid.col_len), (2, 2));
+
+        let err = Grid::<char>::from_str("abc\nab\nabc").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to build grid: row 1 has 2 values but the rows before it have 3"
+        );
+        assert!(matches!(
+            Grid::<char>::from_str("\n\n"),
+            Err(GridError::Empty)
+        ));
+
+        let err = Grid::<u8>::from_str("12\r\n3x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse grid: character 'x' at position (row 1, col 1) could not be parsed \
+             as u8"
+        );
+        match err {
+            GridError::Parse(err) => {
+                assert_eq!((err.row, err.col, err.value.as_str()), (1, 1, "x"))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_str_keeps_edge_spaces() {
+        let grid: Grid<char> = Grid::from_str("\n  #\n ##\n###\n\n").unwrap();
+        assert_eq!((grid.row_len, grid.col_len), (3, 3));
+        assert_eq!(grid.get(0, 0), Some(&' '));
+
+        let grid: Grid<char> = Grid::from_str(" a\nb ").unwrap();
+        assert_eq!(grid.data, vec![' ', 'a', 'b', ' ']);
+        assert_eq!((grid.row_len, grid.col_len), (2, 2));
+    }
+
+    #[test]
+    fn test_from_str_ragged_rows() {
+        assert!(matches!(
+            Grid::<char>::from_str("ab\nc"),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = make_char_grid();
+        assert_eq!(grid.to_string(), "abc\ndef\nghi");
+        assert_eq!(make_i32_grid().to_string(), "123\n456\n789");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let input = "#..\n.#.\n..#";
+        let grid: Grid<char> = Grid::from_str(input).unwrap();
+        let reparsed: Grid<char> = Grid::from_str(&grid.to_string()).unwrap();
+        assert_eq!(grid.to_string(), input);
+        assert_eq!(reparsed.data, grid.data);
+        assert_eq!(reparsed.row_len, grid.row_len);
+        assert_eq!(reparsed.col_len, grid.col_len);
+    }
+
+    #[test]
+    fn test_display_round_trip_with_edge_spaces() {
+        let grid = Grid::from_vecs(&[
+            vec![' ', '#', '#'],
+            vec!['#', ' ', '#'],
+            vec!['#', '#', ' '],
+        ]);
+        assert_eq!(grid.to_string(), " ##\n# #\n## ");
+        let reparsed: Grid<char> = Grid::from_str(&grid.to_string()).unwrap();
+        assert_eq!(reparsed.data, grid.data);
+        assert_eq!(reparsed.row_len, grid.row_len);
+        assert_eq!(reparsed.col_len, grid.col_len);
+    }
+
+    #[test]
+    fn test_display_multi_digit_round_trip() {
+        let grid = Grid::from_vecs(&[vec![10, 20], vec![3, 400]]);
+        assert_eq!(grid.to_string(), " 10  20\n  3 400");
+        let reparsed: Grid<i32> =
+            Grid::try_from_tokens(&grid.to_string(), Separator::Whitespace).unwrap();
+        assert_eq!(reparsed.data, grid.data);
+        assert_eq!(reparsed.row_len, grid.row_len);
+    }
+
+    #[test]
+    fn test_debug() {
+        let grid = make_i32_grid();
+        assert_eq!(
+            format!("{:?}", grid),
+            "Grid { data: [1, 2, 3, 4, 5, 6, 7, 8, 9], row_len: 3, col_len: 3, wrapping: false }"
+        );
+        assert_eq!(
+            format!("{:#?}", make_char_grid()),
+            "    0   1   2\n0 'a' 'b' 'c'\n1 'd' 'e' 'f'\n2 'g' 'h' 'i'"
+        );
+    }
+
     #[test]
     fn test_out_of_bounds() {
         let grid = Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
@@ -389,4 +961,148 @@ mod tests {
         assert_eq!(grid.get_cell(0, 0), Some(Cell::new(1, 0, 0)));
         assert_eq!(grid.get_cell(2, 2), Some(Cell::new(9, 2, 2)));
     }
+
+    #[test]
+    fn test_wrapping_get_cell() {
+        let grid = make_i32_grid().with_wrapping(true);
+        assert_eq!(grid.get_cell(-1, -1), Some(Cell::new(9, 2, 2)));
+        assert_eq!(grid.get_cell(3, 4), Some(Cell::new(2, 0, 1)));
+        assert_eq!(grid.get_cell(-7, 0), Some(Cell::new(7, 2, 0)));
+        assert_eq!(grid.get_value(0, -3), Some(1));
+    }
+
+    #[test]
+    fn test_wrapping_update_cell_value() {
+        let mut grid = make_i32_grid().with_wrapping(true);
+        assert_eq!(grid.update_cell_value(-1, 3, 10), Some(()));
+        assert_eq!(grid.get_value(2, 0), Some(10));
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        let grid = make_i32_grid().with_wrapping(true);
+        assert_eq!(
+            grid.get_cell_neighbor(0, 0, Direction::Up),
+            Some(Cell::new(7, 2, 0))
+        );
+        assert_eq!(
+            grid.get_cell_neighbors(0, 0, Direction::cardinal()),
+            vec![
+                Cell::new(2, 0, 1),
+                Cell::new(4, 1, 0),
+                Cell::new(3, 0, 2),
+                Cell::new(7, 2, 0)
+            ]
+        );
+        assert_eq!(grid.get_cell_neighbors(0, 0, Direction::all()).len(), 8);
+    }
+
+    #[test]
+    fn test_wrapping_segment() {
+        let grid = make_i32_grid().with_wrapping(true);
+        let segment = grid.get_segment(1, 2, Direction::Right, 5).unwrap();
+        assert_eq!(segment.values, vec![6, 4, 5, 6, 4]);
+        assert_eq!(segment.end(), Some(&Cell::new(4, 1, 0)));
+
+        let segment = grid
+            .get_segment(0, 0, Direction::DiagonalUpLeft, 3)
+            .unwrap();
+        assert_eq!(segment.values, vec![1, 9, 5]);
+    }
+
+    #[test]
+    fn test_wrap_coords() {
+        let grid = Grid::from_vecs(&[vec![0; 11], vec![0; 11]]);
+        assert_eq!(grid.wrap_coords(-1, -1), (1, 10));
+        assert_eq!(grid.wrap_coords(5, 23), (1, 1));
+        assert!(!grid.wrapping);
+        assert_eq!(grid.get_cell(-1, -1), None);
+    }
+
+    #[test]
+    fn test_point_accessors() {
+        let mut grid = make_i32_grid();
+        let center = Point::new(1, 1);
+        assert_eq!(grid.get_value_at(center), Some(5));
+        assert_eq!(grid.get_cell_at(center).unwrap().point(), center);
+        assert_eq!(
+            grid.get_cell_neighbor_at(center, Direction::Up),
+            Some(Cell::new(2, 0, 1))
+        );
+        assert_eq!(
+            grid.get_cell_neighbors_at(center, Direction::all()).len(),
+            8
+        );
+        assert_eq!(
+            grid.get_segment_at(center, Direction::Down, 2),
+            grid.get_segment(1, 1, Direction::Down, 2)
+        );
+        assert_eq!(grid.all_segments_at(Point::new(0, 0), 3).len(), 3);
+        assert_eq!(
+            grid.subgrid_at(center, 2, 2).unwrap().data,
+            vec![5, 6, 8, 9]
+        );
+
+        assert!(grid.contains_point(center));
+        assert!(!grid.contains_point(center * 3));
+        assert_eq!(grid.update_cell_value_at(center, 50), Some(()));
+        assert_eq!(grid.get_value(1, 1), Some(50));
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Tile {
+        Wall,
+        Items(Vec<u32>),
+    }
+
+    #[test]
+    fn test_non_copy_values() {
+        let mut grid = Grid::from_vecs(&[
+            vec![Tile::Wall, Tile::Items(vec![1, 2])],
+            vec![Tile::Items(vec![]), Tile::Wall],
+        ]);
+        assert_eq!(grid.get(0, 1), Some(&Tile::Items(vec![1, 2])));
+        assert_eq!(grid.get_value(1, 1), Some(Tile::Wall));
+
+        if let Some(Tile::Items(items)) = grid.get_mut(1, 0) {
+            items.push(7);
+        }
+        assert_eq!(
+            grid.get_cell(1, 0),
+            Some(Cell::new(Tile::Items(vec![7]), 1, 0))
+        );
+        assert_eq!(
+            grid.get_cell_ref(1, 0).unwrap().value,
+            &Tile::Items(vec![7])
+        );
+
+        let walls: Vec<(i32, i32)> = grid
+            .cells()
+            .filter(|cell| *cell.value == Tile::Wall)
+            .map(|cell| (cell.y, cell.x))
+            .collect();
+        assert_eq!(walls, vec![(0, 0), (1, 1)]);
+        assert_eq!(
+            grid.get_cell_neighbors(0, 0, Direction::cardinal()).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_string_grid() {
+        let grid = Grid::from_rows(vec![
+            vec!["ab".to_string(), "c".to_string()],
+            vec!["".to_string(), "def".to_string()],
+        ]);
+        let lengths: Vec<usize> = grid.cells().map(|cell| cell.value.len()).collect();
+        assert_eq!(lengths, vec![2, 1, 0, 3]);
+        assert_eq!(grid.transpose().get(0, 1), Some(&String::new()));
+        assert_eq!(
+            grid.iter_cells()
+                .map(|cell| cell.value)
+                .collect::<Vec<_>>()
+                .concat(),
+            "abcdef"
+        );
+    }
 }
@@ -0,0 +1,358 @@
+use crate::{grid::Grid, gridn::Adjacency};
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::{Add, Sub},
+};
+
+// A (z, y, x) coordinate or the offset between two coordinates
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Point3 {
+    pub z: i32,
+    pub y: i32,
+    pub x: i32,
+}
+
+impl Point3 {
+    pub fn new(z: i32, y: i32, x: i32) -> Self {
+        Self { z, y, x }
+    }
+
+    pub fn manhattan(&self, other: Point3) -> u32 {
+        self.z.abs_diff(other.z) + self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    // The 6 or 26 points around this one, whether or not they are in a grid
+    pub fn neighbors(&self, adjacency: Adjacency) -> Vec<Point3> {
+        adjacency
+            .offsets::<3>()
+            .into_iter()
+            .map(|[z, y, x]| *self + Point3::new(z, y, x))
+            .collect()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.z + other.z, self.y + other.y, self.x + other.x)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.z - other.z, self.y - other.y, self.x - other.x)
+    }
+}
+
+// Like Cell, with the layer z
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Cell3<T: Clone> {
+    pub value: T,
+    pub z: i32,
+    pub y: i32,
+    pub x: i32,
+}
+
+impl<T: Clone> Cell3<T> {
+    pub fn new(value: T, z: i32, y: i32, x: i32) -> Self {
+        Self { value, z, y, x }
+    }
+
+    pub fn point(&self) -> Point3 {
+        Point3::new(self.z, self.y, self.x)
+    }
+}
+
+// A stack of depth layers, each a row_len by col_len grid, stored one layer after another
+#[derive(Clone, Debug)]
+pub struct Grid3<T: Clone> {
+    pub data: Vec<T>,
+    pub row_len: usize,
+    pub col_len: usize,
+    pub depth: usize,
+}
+
+impl<T: Clone> Grid3<T> {
+    pub fn filled(depth: usize, col_len: usize, row_len: usize, value: T) -> Self {
+        Self {
+            data: vec![value; depth * col_len * row_len],
+            row_len,
+            col_len,
+            depth,
+        }
+    }
+
+    // Stack 2D layers bottom to top. None if there are none or their sizes differ
+    pub fn from_layers(layers: &[Grid<T>]) -> Option<Self> {
+        let first = layers.first()?;
+        if layers
+            .iter()
+            .any(|layer| layer.row_len != first.row_len || layer.col_len != first.col_len)
+        {
+            return None;
+        }
+        Some(Self {
+            data: layers
+                .iter()
+                .flat_map(|layer| layer.data.iter().cloned())
+                .collect(),
+            row_len: first.row_len,
+            col_len: first.col_len,
+            depth: layers.len(),
+        })
+    }
+
+    fn is_in_bounds(&self, z: i32, y: i32, x: i32) -> bool {
+        z >= 0
+            && y >= 0
+            && x >= 0
+            && (z as usize) < self.depth
+            && (y as usize) < self.col_len
+            && (x as usize) < self.row_len
+    }
+
+    fn index(&self, z: i32, y: i32, x: i32) -> Option<usize> {
+        self.is_in_bounds(z, y, x)
+            .then(|| (z as usize * self.col_len + y as usize) * self.row_len + x as usize)
+    }
+
+    pub fn get(&self, z: i32, y: i32, x: i32) -> Option<&T> {
+        self.index(z, y, x).map(|index| &self.data[index])
+    }
+
+    pub fn get_value(&self, z: i32, y: i32, x: i32) -> Option<T> {
+        self.get(z, y, x).cloned()
+    }
+
+    pub fn update_cell_value(&mut self, z: i32, y: i32, x: i32, value: T) -> Option<()> {
+        let index = self.index(z, y, x)?;
+        self.data[index] = value;
+        Some(())
+    }
+
+    pub fn get_cell(&self, z: i32, y: i32, x: i32) -> Option<Cell3<T>> {
+        self.get_value(z, y, x)
+            .map(|value| Cell3::new(value, z, y, x))
+    }
+
+    pub fn get_cell_at(&self, point: Point3) -> Option<Cell3<T>> {
+        self.get_cell(point.z, point.y, point.x)
+    }
+
+    // Layer by layer, each in row-major order
+    pub fn iter_cells(&self) -> impl Iterator<Item = Cell3<T>> + '_ {
+        let layer_len = self.row_len * self.col_len;
+        self.data.iter().enumerate().map(move |(i, value)| {
+            Cell3::new(
+                value.clone(),
+                (i / layer_len) as i32,
+                (i % layer_len / self.row_len) as i32,
+                (i % self.row_len) as i32,
+            )
+        })
+    }
+
+    pub fn get_cell_neighbors(
+        &self,
+        z: i32,
+        y: i32,
+        x: i32,
+        adjacency: Adjacency,
+    ) -> Vec<Cell3<T>> {
+        Point3::new(z, y, x)
+            .neighbors(adjacency)
+            .into_iter()
+            .filter_map(|point| self.get_cell_at(point))
+            .collect()
+    }
+
+    // Layer z as a 2D Grid
+    pub fn layer(&self, z: i32) -> Option<Grid<T>> {
+        if !self.is_in_bounds(z, 0, 0) {
+            return None;
+        }
+        let layer_len = self.row_len * self.col_len;
+        let start = z as usize * layer_len;
+        Some(Grid {
+            data: self.data[start..start + layer_len].to_vec(),
+            row_len: self.row_len,
+            col_len: self.col_len,
+            wrapping: false,
+        })
+    }
+
+    pub fn layers(&self) -> impl Iterator<Item = Grid<T>> + '_ {
+        (0..self.depth as i32).map(move |z| self.layer(z).unwrap())
+    }
+
+    // Points reachable from start through cells where is_passable is true, start included.
+    // Empty if start is out of bounds or not passable
+    pub fn flood_fill<F>(
+        &self,
+        start: Point3,
+        adjacency: Adjacency,
+        is_passable: F,
+    ) -> HashSet<Point3>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut filled = HashSet::new();
+        if !self
+            .get(start.z, start.y, start.x)
+            .is_some_and(&is_passable)
+        {
+            return filled;
+        }
+        filled.insert(start);
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            for neighbor in point.neighbors(adjacency) {
+                let passable = self
+                    .get(neighbor.z, neighbor.y, neighbor.x)
+                    .is_some_and(&is_passable);
+                if passable && filled.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        filled
+    }
+
+    // Faces of solid cells that touch a non-solid cell or the outside of the grid,
+    // including the faces around enclosed air pockets
+    pub fn surface_area<F>(&self, is_solid: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter_cells()
+            .filter(|cell| is_solid(&cell.value))
+            .map(|cell| {
+                cell.point()
+                    .neighbors(Adjacency::Faces)
+                    .into_iter()
+                    .filter(|n| !self.get(n.z, n.y, n.x).is_some_and(&is_solid))
+                    .count()
+            })
+            .sum()
+    }
+
+    // Like surface_area, but only counting faces that can be reached from outside the grid,
+    // so enclosed air pockets do not count
+    pub fn exterior_surface_area<F>(&self, is_solid: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        // Flood the air in a box one cell larger than the grid on every side, counting every
+        // time the flood runs into a solid face
+        let in_box = |p: &Point3| {
+            (-1..=self.depth as i32).contains(&p.z)
+                && (-1..=self.col_len as i32).contains(&p.y)
+                && (-1..=self.row_len as i32).contains(&p.x)
+        };
+        let is_solid_at = |p: &Point3| self.get(p.z, p.y, p.x).is_some_and(&is_solid);
+
+        let start = Point3::new(-1, -1, -1);
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut faces = 0;
+        while let Some(point) = queue.pop_front() {
+            for neighbor in point.neighbors(Adjacency::Faces) {
+                if !in_box(&neighbor) {
+                    continue;
+                }
+                if is_solid_at(&neighbor) {
+                    faces += 1;
+                } else if seen.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        faces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The droplet from Advent of Code 2022 day 18
+    const DROPLET: &str = "2,2,2 1,2,2 3,2,2 2,1,2 2,3,2 2,2,1 2,2,3 2,2,4 2,2,6 1,2,5 3,2,5 \
+        2,1,5 2,3,5";
+
+    fn make_droplet() -> Grid3<bool> {
+        let mut grid = Grid3::filled(7, 4, 4, false);
+        for cube in DROPLET.split_whitespace() {
+            let coords: Vec<i32> = cube.split(',').map(|n| n.parse().unwrap()).collect();
+            grid.update_cell_value(coords[2], coords[1], coords[0], true);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_get_and_update() {
+        let mut grid = Grid3::filled(2, 3, 4, 0);
+        assert_eq!(grid.data.len(), 24);
+        assert_eq!(grid.update_cell_value(1, 2, 3, 7), Some(()));
+        assert_eq!(grid.get_value(1, 2, 3), Some(7));
+        assert_eq!(grid.data[23], 7);
+        assert_eq!(grid.get_value(2, 0, 0), None);
+        assert_eq!(grid.update_cell_value(0, -1, 0, 1), None);
+        assert_eq!(grid.iter_cells().last(), Some(Cell3::new(7, 1, 2, 3)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid3::filled(3, 3, 3, 0);
+        assert_eq!(grid.get_cell_neighbors(1, 1, 1, Adjacency::Faces).len(), 6);
+        assert_eq!(grid.get_cell_neighbors(1, 1, 1, Adjacency::All).len(), 26);
+        assert_eq!(grid.get_cell_neighbors(0, 0, 0, Adjacency::Faces).len(), 3);
+        assert_eq!(grid.get_cell_neighbors(0, 0, 0, Adjacency::All).len(), 7);
+    }
+
+    #[test]
+    fn test_layers() {
+        let bottom = Grid::from_vecs(&[vec![1, 2], vec![3, 4]]);
+        let top = Grid::from_vecs(&[vec![5, 6], vec![7, 8]]);
+        let grid = Grid3::from_layers(&[bottom.clone(), top]).unwrap();
+        assert_eq!(grid.depth, 2);
+        assert_eq!(grid.get_value(1, 0, 1), Some(6));
+        assert_eq!(grid.layer(0).unwrap().data, bottom.data);
+        assert!(grid.layer(2).is_none());
+        assert_eq!(grid.layers().count(), 2);
+
+        let narrow = Grid::from_vecs(&[vec![1], vec![2]]);
+        assert!(Grid3::from_layers(&[bottom, narrow]).is_none());
+        assert!(Grid3::<i32>::from_layers(&[]).is_none());
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut grid = Grid3::filled(3, 3, 3, '.');
+        // Wall off the top layer
+        for y in 0..3 {
+            for x in 0..3 {
+                grid.update_cell_value(1, y, x, '#');
+            }
+        }
+        let filled = grid.flood_fill(Point3::default(), Adjacency::Faces, |&c| c == '.');
+        assert_eq!(filled.len(), 9);
+        assert!(grid
+            .flood_fill(Point3::new(1, 0, 0), Adjacency::Faces, |&c| c == '.')
+            .is_empty());
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let grid = make_droplet();
+        assert_eq!(grid.surface_area(|&solid| solid), 64);
+        assert_eq!(grid.exterior_surface_area(|&solid| solid), 58);
+
+        let mut pair = Grid3::filled(1, 1, 2, true);
+        assert_eq!(pair.surface_area(|&solid| solid), 10);
+        pair.update_cell_value(0, 0, 1, false);
+        assert_eq!(pair.exterior_surface_area(|&solid| solid), 6);
+    }
+}
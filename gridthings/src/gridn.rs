@@ -0,0 +1,219 @@
+use crate::grid::Grid;
+
+// Which cells count as neighbors. Faces is the 6 in 3D or 2 * N in general, like
+// Direction::cardinal(). All also counts edges and corners, 26 in 3D or 3^N - 1
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+pub enum Adjacency {
+    #[default]
+    Faces,
+    All,
+}
+
+impl Adjacency {
+    // Offsets to every neighbor, ordered by axis with negative steps first
+    pub fn offsets<const N: usize>(&self) -> Vec<[i32; N]> {
+        match self {
+            Adjacency::Faces => (0..N)
+                .flat_map(|axis| {
+                    [-1, 1].map(|step| {
+                        let mut offset = [0; N];
+                        offset[axis] = step;
+                        offset
+                    })
+                })
+                .collect(),
+            Adjacency::All => (0..3usize.pow(N as u32))
+                .map(|mut i| {
+                    let mut offset = [0; N];
+                    for axis in (0..N).rev() {
+                        offset[axis] = (i % 3) as i32 - 1;
+                        i /= 3;
+                    }
+                    offset
+                })
+                .filter(|offset| offset.iter().any(|&step| step != 0))
+                .collect(),
+        }
+    }
+}
+
+// A dense grid with N axes, for hypercube puzzles like 4D game of life. dims[i] is the
+// length of axis i and data is stored with the last axis changing fastest, so a
+// GridN<T, 2> has the same layout as a Grid with dims [col_len, row_len]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridN<T: Clone, const N: usize> {
+    pub data: Vec<T>,
+    pub dims: [usize; N],
+}
+
+impl<T: Clone, const N: usize> GridN<T, N> {
+    pub fn filled(dims: [usize; N], value: T) -> Self {
+        Self {
+            data: vec![value; dims.iter().product()],
+            dims,
+        }
+    }
+
+    // The 2D grid as the slice where every axis before the last two is 0. Using it with N
+    // below 2 fails to compile
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        const { assert!(N >= 2, "GridN::from_grid needs at least 2 axes") };
+        let mut dims = [1; N];
+        dims[N - 2] = grid.col_len;
+        dims[N - 1] = grid.row_len;
+        Self {
+            data: grid.data.clone(),
+            dims,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn index(&self, coords: [i32; N]) -> Option<usize> {
+        let mut index = 0;
+        for (&coord, &len) in coords.iter().zip(self.dims.iter()) {
+            if coord < 0 || coord as usize >= len {
+                return None;
+            }
+            index = index * len + coord as usize;
+        }
+        Some(index)
+    }
+
+    fn coords(&self, mut index: usize) -> [i32; N] {
+        let mut coords = [0; N];
+        for axis in (0..N).rev() {
+            coords[axis] = (index % self.dims[axis]) as i32;
+            index /= self.dims[axis];
+        }
+        coords
+    }
+
+    pub fn get(&self, coords: [i32; N]) -> Option<&T> {
+        self.index(coords).map(|index| &self.data[index])
+    }
+
+    pub fn get_value(&self, coords: [i32; N]) -> Option<T> {
+        self.get(coords).cloned()
+    }
+
+    pub fn update_cell_value(&mut self, coords: [i32; N], value: T) -> Option<()> {
+        let index = self.index(coords)?;
+        self.data[index] = value;
+        Some(())
+    }
+
+    // Every coordinate with its value, last axis changing fastest
+    pub fn iter_cells(&self) -> impl Iterator<Item = ([i32; N], &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (self.coords(index), value))
+    }
+
+    // Values of the in-bounds neighbors of coords
+    pub fn neighbors(&self, coords: [i32; N], adjacency: Adjacency) -> Vec<&T> {
+        adjacency
+            .offsets::<N>()
+            .into_iter()
+            .filter_map(|offset| {
+                let mut neighbor = coords;
+                for axis in 0..N {
+                    neighbor[axis] += offset[axis];
+                }
+                self.get(neighbor)
+            })
+            .collect()
+    }
+
+    // A copy with by cells of fill added on both ends of every axis, so a growing automaton
+    // has room to spread. Coordinates shift by by on every axis
+    pub fn padded(&self, by: usize, fill: T) -> Self {
+        let mut dims = self.dims;
+        for len in dims.iter_mut() {
+            *len += 2 * by;
+        }
+        let mut padded = Self::filled(dims, fill);
+        for (coords, value) in self.iter_cells() {
+            padded.update_cell_value(coords.map(|coord| coord + by as i32), value.clone());
+        }
+        padded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::MapParser;
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(
+            Adjacency::Faces.offsets::<2>(),
+            vec![[-1, 0], [1, 0], [0, -1], [0, 1]]
+        );
+        assert_eq!(Adjacency::Faces.offsets::<3>().len(), 6);
+        assert_eq!(Adjacency::All.offsets::<3>().len(), 26);
+        assert_eq!(Adjacency::All.offsets::<4>().len(), 80);
+        assert_eq!(Adjacency::All.offsets::<2>()[0], [-1, -1]);
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        let grid = GridN::filled([2, 3, 4], 0);
+        assert_eq!(grid.len(), 24);
+        for (i, (coords, _)) in grid.iter_cells().enumerate() {
+            assert_eq!(grid.index(coords), Some(i));
+        }
+        assert_eq!(grid.get([1, 2, 4]), None);
+        assert_eq!(grid.get([-1, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let grid = Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6]]);
+        let cube: GridN<i32, 3> = GridN::from_grid(&grid);
+        assert_eq!(cube.dims, [1, 2, 3]);
+        assert_eq!(cube.get_value([0, 1, 2]), Some(6));
+
+        let padded = cube.padded(1, 0);
+        assert_eq!(padded.dims, [3, 4, 5]);
+        assert_eq!(padded.get_value([1, 2, 3]), Some(6));
+        assert_eq!(padded.data.iter().sum::<i32>(), 21);
+    }
+
+    // Conway cubes from Advent of Code 2020 day 17
+    fn conway_cubes<const N: usize>(cycles: usize) -> usize {
+        let (grid, _) = MapParser::new(|c| Some(c == '#'))
+            .parse(".#.\n..#\n###")
+            .unwrap();
+        let mut space: GridN<bool, N> = GridN::from_grid(&grid);
+        for _ in 0..cycles {
+            let current = space.padded(1, false);
+            let mut next = current.clone();
+            for (coords, &active) in current.iter_cells() {
+                let count = current
+                    .neighbors(coords, Adjacency::All)
+                    .into_iter()
+                    .filter(|&&neighbor| neighbor)
+                    .count();
+                next.update_cell_value(coords, count == 3 || (active && count == 2));
+            }
+            space = next;
+        }
+        space.data.iter().filter(|&&active| active).count()
+    }
+
+    #[test]
+    fn test_hypercube_automaton() {
+        assert_eq!(conway_cubes::<3>(1), 11);
+        assert_eq!(conway_cubes::<3>(6), 112);
+        assert_eq!(conway_cubes::<4>(1), 29);
+    }
+}
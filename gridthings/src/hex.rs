@@ -0,0 +1,462 @@
+use crate::direction::DirectionParseError;
+use std::{
+    collections::HashMap,
+    ops::{Add, Mul, Sub},
+};
+
+// Axial hex coordinate. The third cube coordinate is s = -q - r, so q + r + s is always 0
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    // None unless q + r + s == 0
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Option<Self> {
+        (q + r + s == 0).then_some(Self { q, r })
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (i32, i32, i32) {
+        (self.q, self.r, self.s())
+    }
+
+    // Number of steps between the two hexes
+    pub fn distance(&self, other: Hex) -> u32 {
+        let (q, r, s) = (*self - other).cube();
+        q.unsigned_abs().max(r.unsigned_abs()).max(s.unsigned_abs())
+    }
+
+    pub fn step(&self, direction: HexDirection) -> Hex {
+        *self + Hex::from(direction)
+    }
+
+    // In the order of HexDirection::ALL
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|direction| self.step(direction))
+    }
+
+    // Hexes exactly radius steps away, 6 * radius of them, going counter-clockwise from the
+    // one radius steps SouthWest. A radius of 0 is just this hex
+    pub fn ring(&self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let mut hex = *self + Hex::from(HexDirection::SouthWest) * radius as i32;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for direction in HexDirection::ALL {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.step(direction);
+            }
+        }
+        ring
+    }
+
+    // This hex followed by the rings 1 to radius, every hex within radius steps
+    pub fn spiral(&self, radius: u32) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    fn mul(self, scale: i32) -> Hex {
+        Hex::new(self.q * scale, self.r * scale)
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(direction: HexDirection) -> Self {
+        let (q, r) = direction.delta();
+        Hex::new(q, r)
+    }
+}
+
+// The six neighbors of a hex. Variants are named for how they look in a pointy-top layout,
+// a flat-top layout labels the same steps differently, see HexLayout::compass
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    // Counter-clockwise starting from East
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    // (q, r) step in axial coordinates
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::West => (-1, 0),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::SouthEast => (0, 1),
+        }
+    }
+
+    // Position in HexDirection::ALL
+    pub fn index(&self) -> usize {
+        match self {
+            HexDirection::East => 0,
+            HexDirection::NorthEast => 1,
+            HexDirection::NorthWest => 2,
+            HexDirection::West => 3,
+            HexDirection::SouthWest => 4,
+            HexDirection::SouthEast => 5,
+        }
+    }
+
+    pub fn from_index(index: usize) -> HexDirection {
+        HexDirection::ALL[index % 6]
+    }
+
+    // Rotate 60 degrees clockwise
+    pub fn turn_right(&self) -> HexDirection {
+        HexDirection::from_index(self.index() + 5)
+    }
+
+    // Rotate 60 degrees counter-clockwise
+    pub fn turn_left(&self) -> HexDirection {
+        HexDirection::from_index(self.index() + 1)
+    }
+
+    pub fn opposite(&self) -> HexDirection {
+        HexDirection::from_index(self.index() + 3)
+    }
+}
+
+// Whether hexes have a corner (pointy) or an edge (flat) at the top. It decides how
+// directions are named and where hexes are drawn, the axial coordinates are the same
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+pub enum HexLayout {
+    #[default]
+    PointyTop,
+    FlatTop,
+}
+
+impl HexLayout {
+    // Lowercase compass name of the direction in this layout
+    pub fn compass(&self, direction: HexDirection) -> &'static str {
+        match self {
+            HexLayout::PointyTop => ["e", "ne", "nw", "w", "sw", "se"][direction.index()],
+            HexLayout::FlatTop => ["se", "ne", "n", "nw", "sw", "s"][direction.index()],
+        }
+    }
+
+    // Inverse of compass(), ignoring case
+    pub fn parse_direction(&self, s: &str) -> Result<HexDirection, DirectionParseError> {
+        HexDirection::ALL
+            .into_iter()
+            .find(|direction| self.compass(*direction).eq_ignore_ascii_case(s))
+            .ok_or_else(|| DirectionParseError {
+                value: s.to_string(),
+            })
+    }
+
+    // A run of compass names with or without separators, like "esenee" or "ne,ne,s".
+    // Two letter names are tried first, which is never ambiguous within one layout
+    pub fn parse_path(&self, s: &str) -> Result<Vec<HexDirection>, DirectionParseError> {
+        let chars: Vec<char> = s
+            .chars()
+            .filter(|c| *c != ',' && !c.is_whitespace())
+            .collect();
+        let mut path = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let pair: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if let (2, Ok(direction)) = (pair.len(), self.parse_direction(&pair)) {
+                path.push(direction);
+                i += 2;
+                continue;
+            }
+            path.push(self.parse_direction(&chars[i].to_string())?);
+            i += 1;
+        }
+        Ok(path)
+    }
+
+    // (y, x) of the hex center for hexes whose corners are size away from the center,
+    // with the origin hex centered on (0, 0) and y growing downward
+    pub fn to_pixel(&self, hex: Hex, size: f64) -> (f64, f64) {
+        let (q, r) = (hex.q as f64, hex.r as f64);
+        let root3 = 3f64.sqrt();
+        match self {
+            HexLayout::PointyTop => (size * 1.5 * r, size * (root3 * q + root3 / 2.0 * r)),
+            HexLayout::FlatTop => (size * (root3 / 2.0 * q + root3 * r), size * 1.5 * q),
+        }
+    }
+}
+
+// Like Cell, for a HexGrid
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct HexCell<T: Clone> {
+    pub value: T,
+    pub q: i32,
+    pub r: i32,
+}
+
+impl<T: Clone> HexCell<T> {
+    pub fn new(value: T, q: i32, r: i32) -> Self {
+        Self { value, q, r }
+    }
+
+    pub fn hex(&self) -> Hex {
+        Hex::new(self.q, self.r)
+    }
+}
+
+// Hex tiles keyed by axial coordinate. Like SparseGrid only the tiles that have been set
+// are stored, so the map can grow in any direction
+#[derive(Clone, Debug, Default)]
+pub struct HexGrid<T: Clone> {
+    pub data: HashMap<Hex, T>,
+    pub layout: HexLayout,
+}
+
+impl<T: Clone> HexGrid<T> {
+    pub fn new(layout: HexLayout) -> Self {
+        Self {
+            data: HashMap::new(),
+            layout,
+        }
+    }
+
+    // A hexagon shaped map of every hex within radius of the origin, all set to value
+    pub fn hexagon(layout: HexLayout, radius: u32, value: T) -> Self {
+        let mut grid = Self::new(layout);
+        for hex in Hex::default().spiral(radius) {
+            grid.data.insert(hex, value.clone());
+        }
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get_value(&self, q: i32, r: i32) -> Option<T> {
+        self.data.get(&Hex::new(q, r)).cloned()
+    }
+
+    pub fn set_value(&mut self, q: i32, r: i32, value: T) {
+        self.data.insert(Hex::new(q, r), value);
+    }
+
+    pub fn remove(&mut self, q: i32, r: i32) -> Option<T> {
+        self.data.remove(&Hex::new(q, r))
+    }
+
+    pub fn get_cell(&self, q: i32, r: i32) -> Option<HexCell<T>> {
+        self.get_value(q, r).map(|value| HexCell::new(value, q, r))
+    }
+
+    // Stored cells ordered by r then q, top row first in either layout
+    pub fn iter_cells(&self) -> impl Iterator<Item = HexCell<T>> + '_ {
+        let mut keys: Vec<&Hex> = self.data.keys().collect();
+        keys.sort_by_key(|hex| (hex.r, hex.q));
+        keys.into_iter()
+            .map(move |hex| HexCell::new(self.data[hex].clone(), hex.q, hex.r))
+    }
+
+    pub fn get_cell_neighbor(&self, q: i32, r: i32, direction: HexDirection) -> Option<HexCell<T>> {
+        let (q_step, r_step) = direction.delta();
+        self.get_cell(q + q_step, r + r_step)
+    }
+
+    // Stored neighbors in the order of directions, e.g. &HexDirection::ALL
+    pub fn get_cell_neighbors(
+        &self,
+        q: i32,
+        r: i32,
+        directions: &[HexDirection],
+    ) -> Vec<HexCell<T>> {
+        directions
+            .iter()
+            .filter_map(|direction| self.get_cell_neighbor(q, r, *direction))
+            .collect()
+    }
+
+    // Coordinates of all six neighbors whether or not they are stored
+    pub fn neighbor_coords(&self, q: i32, r: i32) -> [Hex; 6] {
+        Hex::new(q, r).neighbors()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cube_and_distance() {
+        let hex = Hex::new(2, -3);
+        assert_eq!(hex.cube(), (2, -3, 1));
+        assert_eq!(Hex::from_cube(2, -3, 1), Some(hex));
+        assert_eq!(Hex::from_cube(2, -3, 2), None);
+        assert_eq!(Hex::default().distance(hex), 3);
+        assert_eq!(hex.distance(hex), 0);
+        for direction in HexDirection::ALL {
+            assert_eq!(hex.step(direction).distance(hex), 1);
+            assert_eq!(hex.step(direction).step(direction.opposite()), hex);
+        }
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(HexDirection::East.turn_left(), HexDirection::NorthEast);
+        assert_eq!(HexDirection::East.turn_right(), HexDirection::SouthEast);
+        assert_eq!(HexDirection::NorthWest.opposite(), HexDirection::SouthEast);
+        for (i, direction) in HexDirection::ALL.into_iter().enumerate() {
+            assert_eq!(HexDirection::from_index(i), direction);
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+
+    #[test]
+    fn test_ring_and_spiral() {
+        let center = Hex::new(1, 1);
+        assert_eq!(center.ring(0), vec![center]);
+        assert_eq!(center.ring(1).len(), 6);
+        for radius in 1..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|hex| hex.distance(center) == radius));
+        }
+        let spiral = center.spiral(3);
+        assert_eq!(spiral.len(), 37);
+        assert_eq!(spiral[0], center);
+    }
+
+    #[test]
+    fn test_parse_pointy_path() {
+        let layout = HexLayout::PointyTop;
+        let path = layout.parse_path("esenee").unwrap();
+        assert_eq!(
+            path,
+            vec![
+                HexDirection::East,
+                HexDirection::SouthEast,
+                HexDirection::NorthEast,
+                HexDirection::East
+            ]
+        );
+        let end = |s: &str| {
+            layout
+                .parse_path(s)
+                .unwrap()
+                .into_iter()
+                .fold(Hex::default(), |hex, direction| hex.step(direction))
+        };
+        assert_eq!(end("esew"), Hex::new(0, 1));
+        assert_eq!(end("nwwswee"), Hex::default());
+        assert!(layout.parse_path("en").is_err());
+    }
+
+    #[test]
+    fn test_parse_flat_path() {
+        let layout = HexLayout::FlatTop;
+        let distance = |s: &str| {
+            layout
+                .parse_path(s)
+                .unwrap()
+                .into_iter()
+                .fold(Hex::default(), |hex, direction| hex.step(direction))
+                .distance(Hex::default())
+        };
+        assert_eq!(distance("ne,ne,ne"), 3);
+        assert_eq!(distance("ne,ne,sw,sw"), 0);
+        assert_eq!(distance("ne,ne,s,s"), 2);
+        assert_eq!(distance("se,sw,se,sw,sw"), 3);
+        assert_eq!(
+            layout.parse_direction("N").unwrap(),
+            HexDirection::NorthWest
+        );
+        assert!(layout.parse_direction("e").is_err());
+    }
+
+    #[test]
+    fn test_compass_round_trip() {
+        for layout in [HexLayout::PointyTop, HexLayout::FlatTop] {
+            for direction in HexDirection::ALL {
+                let name = layout.compass(direction);
+                assert_eq!(layout.parse_direction(name).unwrap(), direction);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_pixel() {
+        let (y, x) = HexLayout::PointyTop.to_pixel(Hex::new(1, 0), 1.0);
+        assert_eq!(y, 0.0);
+        assert!((x - 3f64.sqrt()).abs() < 1e-9);
+        let (y, x) = HexLayout::FlatTop.to_pixel(Hex::new(0, 1), 1.0);
+        assert!((y - 3f64.sqrt()).abs() < 1e-9);
+        assert_eq!(x, 0.0);
+    }
+
+    #[test]
+    fn test_hex_grid_neighbors() {
+        let mut grid = HexGrid::hexagon(HexLayout::PointyTop, 1, false);
+        assert_eq!(grid.len(), 7);
+        grid.set_value(1, 0, true);
+        grid.set_value(0, 1, true);
+
+        let neighbors = grid.get_cell_neighbors(0, 0, &HexDirection::ALL);
+        assert_eq!(neighbors.len(), 6);
+        assert_eq!(neighbors.iter().filter(|cell| cell.value).count(), 2);
+        assert_eq!(
+            grid.get_cell_neighbor(0, 0, HexDirection::East),
+            Some(HexCell::new(true, 1, 0))
+        );
+        // Edge hexes only have the neighbors that are stored
+        assert_eq!(grid.get_cell_neighbors(1, 0, &HexDirection::ALL).len(), 3);
+        assert_eq!(grid.neighbor_coords(1, 0).len(), 6);
+
+        let first = grid.iter_cells().next().unwrap();
+        assert_eq!(first.hex(), Hex::new(0, -1));
+        assert_eq!(grid.remove(1, 0), Some(true));
+        assert_eq!(grid.get_value(1, 0), None);
+    }
+}
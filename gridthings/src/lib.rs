@@ -1,9 +1,37 @@
+mod automaton;
 mod cell;
 mod direction;
 mod grid;
+mod grid3;
+mod gridn;
+mod hex;
+mod line;
+mod parse;
+mod pathfinding;
+mod pattern;
+mod point;
+mod ray;
+mod region;
+mod render;
 mod segment;
+mod sparse;
+mod transform;
+mod word_search;
 
+pub use automaton::{AutomatonEnd, Neighbors};
 pub use cell::Cell;
-pub use direction::Direction;
-pub use grid::{Grid, GridParseError};
+pub use direction::{Direction, DirectionParseError, DirectionSet, IntoDirections};
+pub use grid::{Grid, GridError, GridParseError};
+pub use grid3::{Cell3, Grid3, Point3};
+pub use gridn::{Adjacency, GridN};
+pub use hex::{Hex, HexCell, HexDirection, HexGrid, HexLayout};
+pub use line::{bresenham, lattice_step};
+pub use parse::{MapParser, Markers, Separator};
+pub use pathfinding::{Pose, PoseSearchResult, SearchResult, TurnCosts};
+pub use pattern::Pattern;
+pub use point::Point;
+pub use ray::RayStop;
+pub use region::{Region, Regions};
+pub use render::{Color, RenderOptions, Style};
 pub use segment::Segment;
+pub use sparse::{SparseCell, SparseGrid, SparseSegment};
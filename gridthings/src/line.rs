@@ -0,0 +1,205 @@
+use crate::{grid::Grid, point::Point};
+
+// Cells a straight segment from a to b passes through, both ends included, so that
+// consecutive points touch in one of the 8 directions
+pub fn bresenham(a: Point, b: Point) -> Vec<Point> {
+    let dy = -(b.y - a.y).abs();
+    let dx = (b.x - a.x).abs();
+    let y_step = if a.y < b.y { 1 } else { -1 };
+    let x_step = if a.x < b.x { 1 } else { -1 };
+
+    let mut points = Vec::new();
+    let mut current = a;
+    let mut error = dx + dy;
+    loop {
+        points.push(current);
+        if current == b {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            current.x += x_step;
+        }
+        if doubled <= dx {
+            error += dx;
+            current.y += y_step;
+        }
+    }
+    points
+}
+
+// The smallest integer step along the line from a to b, (0, 0) if they are the same point
+pub fn lattice_step(a: Point, b: Point) -> Point {
+    let delta = b - a;
+    let divisor = gcd(delta.y.abs(), delta.x.abs()).max(1);
+    Point::new(delta.y / divisor, delta.x / divisor)
+}
+
+fn floor_div(n: i32, d: i32) -> i32 {
+    match d > 0 {
+        true => n.div_euclid(d),
+        false => (-n).div_euclid(-d),
+    }
+}
+
+fn ceil_div(n: i32, d: i32) -> i32 {
+    -floor_div(-n, d)
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Every integer point on the infinite line through a and b that lies inside the grid,
+    // ordered from one edge to the other in the direction of a to b. Wrapping is ignored
+    pub fn lattice_line(&self, a: Point, b: Point) -> Vec<Point> {
+        let step = lattice_step(a, b);
+        if step == Point::default() {
+            return match self.is_in_bounds(a.y, a.x) {
+                true => vec![a],
+                false => Vec::new(),
+            };
+        }
+
+        // Intersect the range of k where a + k * step stays inside on each axis
+        let mut k_min = i32::MIN;
+        let mut k_max = i32::MAX;
+        let axes = [
+            (a.y, step.y, self.col_len as i32),
+            (a.x, step.x, self.row_len as i32),
+        ];
+        for (position, step, len) in axes {
+            if step == 0 {
+                if position < 0 || position >= len {
+                    return Vec::new();
+                }
+                continue;
+            }
+            let (low, high) = match step > 0 {
+                true => (-position, len - 1 - position),
+                false => (len - 1 - position, -position),
+            };
+            k_min = k_min.max(ceil_div(low, step));
+            k_max = k_max.min(floor_div(high, step));
+        }
+        (k_min..=k_max).map(|k| a + step * k).collect()
+    }
+
+    // a + k * (b - a) for each k, keeping the points inside the grid. k = -1 and k = 2 are the
+    // points one full gap beyond each end
+    pub fn harmonic_points<I>(&self, a: Point, b: Point, ks: I) -> Vec<Point>
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        let delta = b - a;
+        ks.into_iter()
+            .map(|k| a + delta * k)
+            .filter(|point| self.is_in_bounds(point.y, point.x))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_grid() -> Grid<char> {
+        Grid::from_str(&vec![".".repeat(10); 10].join("\n")).unwrap()
+    }
+
+    #[test]
+    fn test_lattice_step() {
+        assert_eq!(
+            lattice_step(Point::new(1, 1), Point::new(5, 7)),
+            Point::new(2, 3)
+        );
+        assert_eq!(
+            lattice_step(Point::new(0, 0), Point::new(0, -4)),
+            Point::new(0, -1)
+        );
+        assert_eq!(
+            lattice_step(Point::new(2, 2), Point::new(2, 2)),
+            Point::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_lattice_line() {
+        let grid = make_grid();
+        let points = grid.lattice_line(Point::new(4, 4), Point::new(6, 5));
+        assert_eq!(
+            points,
+            vec![
+                Point::new(0, 2),
+                Point::new(2, 3),
+                Point::new(4, 4),
+                Point::new(6, 5),
+                Point::new(8, 6)
+            ]
+        );
+
+        let reversed = grid.lattice_line(Point::new(6, 5), Point::new(4, 4));
+        assert_eq!(reversed.first(), Some(&Point::new(8, 6)));
+        assert_eq!(reversed.len(), 5);
+    }
+
+    #[test]
+    fn test_lattice_line_from_outside() {
+        // Neither point is in the grid but the line through them crosses it
+        let grid = make_grid();
+        let points = grid.lattice_line(Point::new(-20, -20), Point::new(-19, -19));
+        assert_eq!(points.len(), 10);
+        assert_eq!(points[0], Point::new(0, 0));
+        assert_eq!(points[9], Point::new(9, 9));
+
+        assert!(grid
+            .lattice_line(Point::new(-1, 0), Point::new(-1, 5))
+            .is_empty());
+        assert_eq!(
+            grid.lattice_line(Point::new(3, 3), Point::new(3, 3)),
+            vec![Point::new(3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_harmonic_points() {
+        let grid = make_grid();
+        let a = Point::new(3, 4);
+        let b = Point::new(5, 5);
+        assert_eq!(
+            grid.harmonic_points(a, b, [-1, 2]),
+            vec![Point::new(1, 3), Point::new(7, 6)]
+        );
+        assert!(grid.harmonic_points(a, b, [-2, 4]).is_empty());
+    }
+
+    #[test]
+    fn test_bresenham() {
+        assert_eq!(
+            bresenham(Point::new(0, 0), Point::new(2, 4)),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 1),
+                Point::new(1, 2),
+                Point::new(2, 3),
+                Point::new(2, 4)
+            ]
+        );
+        let points = bresenham(Point::new(5, 5), Point::new(1, 2));
+        assert_eq!(points.first(), Some(&Point::new(5, 5)));
+        assert_eq!(points.last(), Some(&Point::new(1, 2)));
+        for pair in points.windows(2) {
+            assert_eq!(pair[0].chebyshev(pair[1]), 1);
+        }
+        assert_eq!(
+            bresenham(Point::new(1, 1), Point::new(1, 1)),
+            vec![Point::new(1, 1)]
+        );
+    }
+}
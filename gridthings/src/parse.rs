@@ -0,0 +1,270 @@
+use crate::{
+    grid::{Grid, GridError, GridParseError},
+    point::Point,
+};
+use std::{collections::HashMap, str::FromStr};
+
+// How Grid::try_from_tokens splits a line into cells
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Separator {
+    // Runs of spaces or tabs, e.g. "08 02 22"
+    Whitespace,
+    // A single character such as ','. Spaces around each token are ignored
+    Char(char),
+    // Every width chars make one token, the last one may be shorter. Padding is ignored
+    FixedWidth(usize),
+}
+
+impl Separator {
+    fn split<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        match *self {
+            Separator::Whitespace => line.split_whitespace().collect(),
+            Separator::Char(c) => line.split(c).map(str::trim).collect(),
+            Separator::FixedWidth(width) => {
+                let mut starts: Vec<usize> = line
+                    .char_indices()
+                    .step_by(width)
+                    .map(|(idx, _)| idx)
+                    .collect();
+                starts.push(line.len());
+                starts
+                    .windows(2)
+                    .map(|bounds| line[bounds[0]..bounds[1]].trim())
+                    .collect()
+            }
+        }
+    }
+}
+
+// The lines of input without blank lines at the start or end. Unlike str::trim this keeps
+// the leading spaces of the first line, which matter for fixed width columns
+pub(crate) fn content_lines(input: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let first = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    lines.split_off(first)
+}
+
+impl<T: Clone + FromStr> Grid<T> {
+    // One token per cell instead of one char, for inputs like space separated number
+    // matrices. Parse errors report the token index as col
+    pub fn try_from_tokens(input: &str, separator: Separator) -> Result<Self, GridError> {
+        if separator == Separator::FixedWidth(0) {
+            return Err(GridError::ZeroWidth);
+        }
+        let mut data = Vec::new();
+        for (row_idx, line) in content_lines(input).into_iter().enumerate() {
+            let mut row = Vec::new();
+            for (col_idx, token) in separator.split(line).into_iter().enumerate() {
+                match token.parse() {
+                    Ok(value) => row.push(value),
+                    Err(_) => {
+                        return Err(GridError::Parse(GridParseError {
+                            row: row_idx,
+                            col: col_idx,
+                            value: token.to_string(),
+                            target_type: std::any::type_name::<T>().to_string(),
+                        }))
+                    }
+                }
+            }
+            data.push(row);
+        }
+        Self::try_from_rows(data)
+    }
+}
+
+// Positions of each marker char found by MapParser
+pub type Markers = HashMap<char, Vec<Point>>;
+
+// Builds a Grid from one char per cell through a mapping closure, e.g. into a tile enum.
+// Marker chars like a '^' start or 'S'/'E' endpoints have their positions recorded while
+// parsing, and can be swapped for a fill value so the grid only holds the plain tile
+pub struct MapParser<T, F> {
+    map: F,
+    markers: HashMap<char, Option<T>>,
+}
+
+impl<T, F> MapParser<T, F>
+where
+    T: Clone,
+    F: Fn(char) -> Option<T>,
+{
+    // map returns None for chars that are not allowed in the grid
+    pub fn new(map: F) -> Self {
+        Self {
+            map,
+            markers: HashMap::new(),
+        }
+    }
+
+    // Record where c appears and store it in the grid as map(c)
+    pub fn marker(mut self, c: char) -> Self {
+        self.markers.insert(c, None);
+        self
+    }
+
+    // Record where c appears and store fill in its place
+    pub fn marker_with_fill(mut self, c: char, fill: T) -> Self {
+        self.markers.insert(c, Some(fill));
+        self
+    }
+
+    // The grid and the positions of each marker in row-major order. Markers that never
+    // appear have no entry
+    pub fn parse(&self, input: &str) -> Result<(Grid<T>, Markers), GridError> {
+        let mut data = Vec::new();
+        let mut found = Markers::new();
+        for (row_idx, line) in content_lines(input).into_iter().enumerate() {
+            let mut row = Vec::new();
+            for (col_idx, c) in line.chars().enumerate() {
+                let fill = self.markers.get(&c);
+                if fill.is_some() {
+                    found
+                        .entry(c)
+                        .or_default()
+                        .push(Point::new(row_idx as i32, col_idx as i32));
+                }
+                match fill.cloned().flatten().or_else(|| (self.map)(c)) {
+                    Some(value) => row.push(value),
+                    None => {
+                        return Err(GridError::Parse(GridParseError {
+                            row: row_idx,
+                            col: col_idx,
+                            value: c.to_string(),
+                            target_type: std::any::type_name::<T>().to_string(),
+                        }))
+                    }
+                }
+            }
+            data.push(row);
+        }
+        Ok((Grid::try_from_rows(data)?, found))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitespace_tokens() {
+        let grid: Grid<u32> =
+            Grid::try_from_tokens("08 02  22\n\t49 49 99\r\n", Separator::Whitespace).unwrap();
+        assert_eq!(grid.data, vec![8, 2, 22, 49, 49, 99]);
+        assert_eq!((grid.row_len, grid.col_len), (3, 2));
+    }
+
+    #[test]
+    fn test_char_tokens() {
+        let grid: Grid<i64> =
+            Grid::try_from_tokens("1, -2,3\n40,5 ,6", Separator::Char(',')).unwrap();
+        assert_eq!(grid.data, vec![1, -2, 3, 40, 5, 6]);
+
+        let grid: Grid<String> = Grid::try_from_tokens("ab|c\nd|ef", Separator::Char('|')).unwrap();
+        assert_eq!(grid.get(1, 1), Some(&"ef".to_string()));
+    }
+
+    #[test]
+    fn test_fixed_width_tokens() {
+        let grid: Grid<u32> =
+            Grid::try_from_tokens("\n  1 20300\n 10  5  6\n\n", Separator::FixedWidth(3)).unwrap();
+        assert_eq!(grid.data, vec![1, 20, 300, 10, 5, 6]);
+
+        let grid: Grid<char> = Grid::try_from_tokens("ab\ncd", Separator::FixedWidth(1)).unwrap();
+        assert_eq!(grid.data, vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn test_token_errors() {
+        match Grid::<u32>::try_from_tokens("1 2 3\n4 five 6", Separator::Whitespace) {
+            Err(GridError::Parse(err)) => {
+                assert_eq!((err.row, err.col), (1, 1));
+                assert_eq!(err.value, "five");
+                assert_eq!(
+                    err.to_string(),
+                    "Failed to parse grid: value 'five' at position (row 1, col 1) could not be \
+                     parsed as u32"
+                );
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(matches!(
+            Grid::<u32>::try_from_tokens("1,2,3\n4,5", Separator::Char(',')),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            Grid::<u32>::try_from_tokens("  \n", Separator::Whitespace),
+            Err(GridError::Empty)
+        ));
+        assert!(matches!(
+            Grid::<u32>::try_from_tokens("123", Separator::FixedWidth(0)),
+            Err(GridError::ZeroWidth)
+        ));
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    fn tile(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Open),
+            '#' => Some(Tile::Wall),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_map_parser_markers() {
+        let (grid, markers) = MapParser::new(tile)
+            .marker_with_fill('S', Tile::Open)
+            .marker_with_fill('E', Tile::Open)
+            .marker_with_fill('o', Tile::Open)
+            .parse("#S.#\n#..E\n#.o.\n..o#")
+            .unwrap();
+        assert_eq!(grid.get(0, 1), Some(&Tile::Open));
+        assert_eq!(grid.get(0, 3), Some(&Tile::Wall));
+        assert_eq!(markers[&'S'], vec![Point::new(0, 1)]);
+        assert_eq!(markers[&'E'], vec![Point::new(1, 3)]);
+        assert_eq!(markers[&'o'], vec![Point::new(2, 2), Point::new(3, 2)]);
+    }
+
+    #[test]
+    fn test_map_parser_marker_without_fill() {
+        let (grid, markers) = MapParser::new(Some)
+            .marker('^')
+            .marker('X')
+            .parse("..#\n.^.\n")
+            .unwrap();
+        assert_eq!(grid.get_value_at(markers[&'^'][0]), Some('^'));
+        assert!(!markers.contains_key(&'X'));
+    }
+
+    #[test]
+    fn test_map_parser_errors() {
+        match MapParser::new(tile).parse("..\n.?") {
+            Err(GridError::Parse(err)) => {
+                assert_eq!((err.row, err.col, err.value.as_str()), (1, 1, "?"))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        // Marker chars need a fill when the closure does not map them
+        assert!(MapParser::new(tile).marker('S').parse("S.").is_err());
+        assert!(matches!(
+            MapParser::new(tile).parse("..\n."),
+            Err(GridError::RaggedRow { row: 1, .. })
+        ));
+    }
+}
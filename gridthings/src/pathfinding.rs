@@ -0,0 +1,606 @@
+use crate::{
+    cell::Cell,
+    direction::{Direction, DirectionSet},
+    grid::Grid,
+    point::Point,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+};
+
+// Distances and predecessors from a single start cell, keyed by (y, x).
+// explored counts how many cells were expanded, goal is the goal reached by a targeted search
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub start: (i32, i32),
+    pub distances: HashMap<(i32, i32), u32>,
+    pub predecessors: HashMap<(i32, i32), (i32, i32)>,
+    pub explored: usize,
+    pub goal: Option<(i32, i32)>,
+}
+
+// A position plus the Direction it is facing, for searches where turning has a cost
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Pose {
+    pub y: i32,
+    pub x: i32,
+    pub direction: Direction,
+}
+
+impl Pose {
+    pub fn new(y: i32, x: i32, direction: Direction) -> Self {
+        Self { y, x, direction }
+    }
+
+    pub fn point(&self) -> Point {
+        Point::new(self.y, self.x)
+    }
+}
+
+// Cost of stepping one cell in the facing direction and of rotating 90 degrees in place
+#[derive(Clone, Debug)]
+pub struct TurnCosts {
+    pub forward: u32,
+    pub turn_left: u32,
+    pub turn_right: u32,
+}
+
+// Lowest cost to reach any goal and every (y, x) that lies on at least one optimal path
+#[derive(Clone, Debug)]
+pub struct PoseSearchResult {
+    pub cost: Option<u32>,
+    pub cells: HashSet<(i32, i32)>,
+    pub explored: usize,
+}
+
+impl SearchResult {
+    fn new(y: i32, x: i32) -> Self {
+        let mut distances = HashMap::new();
+        distances.insert((y, x), 0);
+        Self {
+            start: (y, x),
+            distances,
+            predecessors: HashMap::new(),
+            explored: 0,
+            goal: None,
+        }
+    }
+
+    pub fn distance(&self, y: i32, x: i32) -> Option<u32> {
+        self.distances.get(&(y, x)).copied()
+    }
+
+    pub fn distance_at(&self, point: Point) -> Option<u32> {
+        self.distance(point.y, point.x)
+    }
+
+    // Walk the predecessor map back from (y, x) and return the cells from start to (y, x)
+    pub fn path_to<T: Clone>(&self, grid: &Grid<T>, y: i32, x: i32) -> Option<Vec<Cell<T>>> {
+        if !self.distances.contains_key(&(y, x)) {
+            return None;
+        }
+        let mut path = vec![grid.get_cell(y, x)?];
+        let mut current = (y, x);
+        while let Some(&(prev_y, prev_x)) = self.predecessors.get(&current) {
+            path.push(grid.get_cell(prev_y, prev_x)?);
+            current = (prev_y, prev_x);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn path_to_at<T: Clone>(&self, grid: &Grid<T>, point: Point) -> Option<Vec<Cell<T>>> {
+        self.path_to(grid, point.y, point.x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Unweighted search. is_passable is called with (from, to) for every candidate step
+    pub fn bfs<D, F>(&self, y: i32, x: i32, directions: D, is_passable: F) -> SearchResult
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> bool,
+    {
+        let directions = directions.into();
+        let mut result = SearchResult::new(y, x);
+        let Some(start) = self.get_cell(y, x) else {
+            result.distances.clear();
+            return result;
+        };
+
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        while let Some(cell) = queue.pop_front() {
+            result.explored += 1;
+            let distance = result.distances[&(cell.y, cell.x)];
+            for neighbor in self.get_cell_neighbors(cell.y, cell.x, directions) {
+                if result.distances.contains_key(&(neighbor.y, neighbor.x)) {
+                    continue;
+                }
+                if !is_passable(&cell, &neighbor) {
+                    continue;
+                }
+                result
+                    .distances
+                    .insert((neighbor.y, neighbor.x), distance + 1);
+                result
+                    .predecessors
+                    .insert((neighbor.y, neighbor.x), (cell.y, cell.x));
+                queue.push_back(neighbor);
+            }
+        }
+        result
+    }
+
+    pub fn bfs_at<D, F>(&self, start: Point, directions: D, is_passable: F) -> SearchResult
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> bool,
+    {
+        self.bfs(start.y, start.x, directions, is_passable)
+    }
+
+    // Weighted search. cost is called with (from, to) and returns None when the step is blocked
+    pub fn dijkstra<D, F>(&self, y: i32, x: i32, directions: D, cost: F) -> SearchResult
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> Option<u32>,
+    {
+        let directions = directions.into();
+        let mut result = SearchResult::new(y, x);
+        let Some(start) = self.get_cell(y, x) else {
+            result.distances.clear();
+            return result;
+        };
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0, start.y, start.x)));
+        while let Some(Reverse((distance, cell_y, cell_x))) = heap.pop() {
+            if distance > result.distances[&(cell_y, cell_x)] {
+                continue;
+            }
+            result.explored += 1;
+            let cell = self.get_cell(cell_y, cell_x).unwrap();
+            for neighbor in self.get_cell_neighbors(cell.y, cell.x, directions) {
+                let Some(step) = cost(&cell, &neighbor) else {
+                    continue;
+                };
+                let next = distance + step;
+                let key = (neighbor.y, neighbor.x);
+                if result
+                    .distances
+                    .get(&key)
+                    .is_some_and(|&known| known <= next)
+                {
+                    continue;
+                }
+                result.distances.insert(key, next);
+                result.predecessors.insert(key, (cell.y, cell.x));
+                heap.push(Reverse((next, neighbor.y, neighbor.x)));
+            }
+        }
+        result
+    }
+
+    pub fn dijkstra_at<D, F>(&self, start: Point, directions: D, cost: F) -> SearchResult
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> Option<u32>,
+    {
+        self.dijkstra(start.y, start.x, directions, cost)
+    }
+
+    // Weighted search that stops at the first goal reached. heuristic is called with
+    // (point, goal), e.g. Point::manhattan, and the smallest estimate across all goals is used. Only expanded cells
+    // are kept in distances and predecessors, the frontier's tentative values are dropped
+    pub fn astar<D, F, H>(
+        &self,
+        y: i32,
+        x: i32,
+        goals: &[(i32, i32)],
+        directions: D,
+        cost: F,
+        heuristic: H,
+    ) -> SearchResult
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> Option<u32>,
+        H: Fn(&Point, Point) -> u32,
+    {
+        let directions = directions.into();
+        let mut result = SearchResult::new(y, x);
+        let Some(start) = self.get_cell(y, x) else {
+            result.distances.clear();
+            return result;
+        };
+        let estimate = |y: i32, x: i32| {
+            goals
+                .iter()
+                .map(|&goal| heuristic(&Point::new(y, x), Point::from(goal)))
+                .min()
+                .unwrap_or(0)
+        };
+
+        let mut expanded = HashSet::new();
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((estimate(start.y, start.x), 0, start.y, start.x)));
+        while let Some(Reverse((_, distance, cell_y, cell_x))) = heap.pop() {
+            if distance > result.distances[&(cell_y, cell_x)] {
+                continue;
+            }
+            expanded.insert((cell_y, cell_x));
+            result.explored += 1;
+            if goals.contains(&(cell_y, cell_x)) {
+                result.goal = Some((cell_y, cell_x));
+                break;
+            }
+            let cell = self.get_cell(cell_y, cell_x).unwrap();
+            for neighbor in self.get_cell_neighbors(cell.y, cell.x, directions) {
+                let Some(step) = cost(&cell, &neighbor) else {
+                    continue;
+                };
+                let next = distance + step;
+                let key = (neighbor.y, neighbor.x);
+                if result
+                    .distances
+                    .get(&key)
+                    .is_some_and(|&known| known <= next)
+                {
+                    continue;
+                }
+                result.distances.insert(key, next);
+                result.predecessors.insert(key, (cell.y, cell.x));
+                let priority = next + estimate(neighbor.y, neighbor.x);
+                heap.push(Reverse((priority, next, neighbor.y, neighbor.x)));
+            }
+        }
+        result.distances.retain(|key, _| expanded.contains(key));
+        result.predecessors.retain(|key, _| expanded.contains(key));
+        result
+    }
+
+    pub fn astar_at<D, F, H>(
+        &self,
+        start: Point,
+        goals: &[Point],
+        directions: D,
+        cost: F,
+        heuristic: H,
+    ) -> SearchResult
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> Option<u32>,
+        H: Fn(&Point, Point) -> u32,
+    {
+        let goals: Vec<(i32, i32)> = goals.iter().map(|&goal| goal.into()).collect();
+        self.astar(start.y, start.x, &goals, directions, cost, heuristic)
+    }
+
+    // Dijkstra over Poses. Moving forward onto a cell requires is_passable(cell), turning
+    // happens in place. Ties are kept so the result covers every optimal path to the goals
+    pub fn pose_search<F>(
+        &self,
+        start: Pose,
+        goals: &[(i32, i32)],
+        costs: &TurnCosts,
+        is_passable: F,
+    ) -> PoseSearchResult
+    where
+        F: Fn(&Cell<T>) -> bool,
+    {
+        let mut result = PoseSearchResult {
+            cost: None,
+            cells: HashSet::new(),
+            explored: 0,
+        };
+        if self.get_cell(start.y, start.x).is_none() {
+            return result;
+        }
+
+        let mut distances: HashMap<Pose, u32> = HashMap::new();
+        let mut predecessors: HashMap<Pose, Vec<Pose>> = HashMap::new();
+        let mut reached_goals = Vec::new();
+        let mut heap = BinaryHeap::new();
+        distances.insert(start.clone(), 0);
+        heap.push(Reverse((0, start)));
+
+        while let Some(Reverse((distance, pose))) = heap.pop() {
+            if distance > distances[&pose] {
+                continue;
+            }
+            if result.cost.is_some_and(|best| distance > best) {
+                break;
+            }
+            result.explored += 1;
+            if goals.contains(&(pose.y, pose.x)) {
+                result.cost = Some(distance);
+                reached_goals.push(pose);
+                continue;
+            }
+
+            let mut candidates = vec![
+                (
+                    Pose::new(pose.y, pose.x, pose.direction.turn_left()),
+                    costs.turn_left,
+                ),
+                (
+                    Pose::new(pose.y, pose.x, pose.direction.turn_right()),
+                    costs.turn_right,
+                ),
+            ];
+            if let Some(cell) = self.get_cell_neighbor(pose.y, pose.x, pose.direction.clone()) {
+                if is_passable(&cell) {
+                    let forward = Pose::new(cell.y, cell.x, pose.direction.clone());
+                    candidates.push((forward, costs.forward));
+                }
+            }
+
+            for (next_pose, step) in candidates {
+                let next = distance + step;
+                match distances.get(&next_pose) {
+                    Some(&known) if known < next => continue,
+                    Some(&known) if known == next => {
+                        predecessors
+                            .entry(next_pose)
+                            .or_default()
+                            .push(pose.clone());
+                        continue;
+                    }
+                    _ => {}
+                }
+                distances.insert(next_pose.clone(), next);
+                predecessors.insert(next_pose.clone(), vec![pose.clone()]);
+                heap.push(Reverse((next, next_pose)));
+            }
+        }
+
+        // Walk every tied predecessor back from the goals to collect the optimal cells
+        let mut seen: HashSet<Pose> = HashSet::new();
+        let mut stack = reached_goals;
+        while let Some(pose) = stack.pop() {
+            if !seen.insert(pose.clone()) {
+                continue;
+            }
+            result.cells.insert((pose.y, pose.x));
+            if let Some(previous) = predecessors.get(&pose) {
+                stack.extend(previous.iter().cloned());
+            }
+        }
+        result
+    }
+
+    pub fn pose_search_at<F>(
+        &self,
+        start: Pose,
+        goals: &[Point],
+        costs: &TurnCosts,
+        is_passable: F,
+    ) -> PoseSearchResult
+    where
+        F: Fn(&Cell<T>) -> bool,
+    {
+        let goals: Vec<(i32, i32)> = goals.iter().map(|&goal| goal.into()).collect();
+        self.pose_search(start, &goals, costs, is_passable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_maze() -> Grid<char> {
+        Grid::from_str("S.#.\n..#.\n#...\n..#E").unwrap()
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let grid = make_maze();
+        let result = grid.bfs(0, 0, Direction::cardinal(), |_, to| to.value != '#');
+        assert_eq!(result.distance(0, 0), Some(0));
+        assert_eq!(result.distance(1, 1), Some(2));
+        assert_eq!(result.distance(3, 3), Some(6));
+        assert_eq!(result.distance(0, 2), None);
+    }
+
+    #[test]
+    fn test_bfs_path_to() {
+        let grid = make_maze();
+        let result = grid.bfs(0, 0, Direction::cardinal(), |_, to| to.value != '#');
+        let path = result.path_to(&grid, 3, 3).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&Cell::new('S', 0, 0)));
+        assert_eq!(path.last(), Some(&Cell::new('E', 3, 3)));
+        assert!(path.iter().all(|cell| cell.value != '#'));
+        assert_eq!(result.path_to(&grid, 0, 2), None);
+    }
+
+    #[test]
+    fn test_bfs_out_of_bounds_start() {
+        let grid = make_maze();
+        let result = grid.bfs(-1, 0, Direction::cardinal(), |_, _| true);
+        assert!(result.distances.is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra_weighted() {
+        // Moving onto a cell costs its value, so the cheap route goes around the 9s
+        let grid: Grid<u32> = Grid::from_str("1911\n1919\n1111").unwrap();
+        let result = grid.dijkstra(0, 0, Direction::cardinal(), |_, to| Some(to.value));
+        assert_eq!(result.distance(0, 3), Some(7));
+        let path = result.path_to(&grid, 0, 3).unwrap();
+        assert!(path.iter().all(|cell| cell.value == 1));
+    }
+
+    #[test]
+    fn test_dijkstra_matches_bfs_on_unit_costs() {
+        let grid = make_maze();
+        let bfs = grid.bfs(0, 0, Direction::cardinal(), |_, to| to.value != '#');
+        let dijkstra = grid.dijkstra(0, 0, Direction::cardinal(), |_, to| {
+            (to.value != '#').then_some(1)
+        });
+        assert_eq!(bfs.distances, dijkstra.distances);
+
+        let start = Point::default();
+        let bfs_at = grid.bfs_at(start, Direction::cardinal(), |_, to| to.value != '#');
+        assert_eq!(bfs_at.distances, bfs.distances);
+        let dijkstra_at = grid.dijkstra_at(start, Direction::cardinal(), |_, to| {
+            (to.value != '#').then_some(1)
+        });
+        assert_eq!(dijkstra_at.distance_at(Point::new(3, 3)), Some(6));
+    }
+
+    #[test]
+    fn test_astar_reaches_goal() {
+        let grid = make_maze();
+        let cost = |_: &Cell<char>, to: &Cell<char>| (to.value != '#').then_some(1);
+        let result = grid.astar(
+            0,
+            0,
+            &[(3, 3)],
+            Direction::cardinal(),
+            cost,
+            Point::manhattan,
+        );
+        assert_eq!(result.goal, Some((3, 3)));
+        assert_eq!(result.distance(3, 3), Some(6));
+        assert_eq!(result.path_to(&grid, 3, 3).unwrap().len(), 7);
+
+        let goal = Point::new(3, 3);
+        let at = grid.astar_at(
+            Point::default(),
+            &[goal],
+            Direction::cardinal(),
+            cost,
+            Point::manhattan,
+        );
+        assert_eq!(at.distance_at(goal), Some(6));
+        assert_eq!(at.path_to_at(&grid, goal), result.path_to(&grid, 3, 3));
+    }
+
+    #[test]
+    fn test_astar_multiple_goals() {
+        let grid = make_maze();
+        let cost = |_: &Cell<char>, to: &Cell<char>| (to.value != '#').then_some(1);
+        let goals = [(3, 3), (3, 0)];
+        let result = grid.astar(0, 0, &goals, Direction::cardinal(), cost, Point::manhattan);
+        assert_eq!(result.goal, Some((3, 0)));
+        assert_eq!(result.distance(3, 0), Some(5));
+    }
+
+    #[test]
+    fn test_astar_unreachable_goal() {
+        let grid = make_maze();
+        let cost = |_: &Cell<char>, to: &Cell<char>| (to.value != '#').then_some(1);
+        let result = grid.astar(
+            0,
+            0,
+            &[(0, 2)],
+            Direction::cardinal(),
+            cost,
+            Point::manhattan,
+        );
+        assert_eq!(result.goal, None);
+        assert_eq!(result.path_to(&grid, 0, 2), None);
+    }
+
+    #[test]
+    fn test_astar_explores_less_than_dijkstra() {
+        let grid: Grid<char> = Grid::from_str(&vec![".".repeat(20); 20].join("\n")).unwrap();
+        let cost = |_: &Cell<char>, _: &Cell<char>| Some(1);
+        let dijkstra = grid.dijkstra(0, 0, Direction::all(), cost);
+        let astar = grid.astar(0, 0, &[(19, 19)], Direction::all(), cost, Point::chebyshev);
+        assert_eq!(astar.distance(19, 19), dijkstra.distance(19, 19));
+        assert!(astar.explored < dijkstra.explored);
+
+        // Around a wall, cardinal moves only
+        let mut rows = vec![".".repeat(20); 20];
+        rows[10] = format!("{}.", "#".repeat(19));
+        let grid: Grid<char> = Grid::from_str(&rows.join("\n")).unwrap();
+        let cost = |_: &Cell<char>, to: &Cell<char>| (to.value != '#').then_some(1);
+        let dijkstra = grid.dijkstra(0, 0, Direction::cardinal(), cost);
+        let astar = grid.astar(
+            0,
+            0,
+            &[(19, 19)],
+            Direction::cardinal(),
+            cost,
+            Point::manhattan,
+        );
+        assert_eq!(astar.distance(19, 19), Some(38));
+        assert_eq!(astar.distance(19, 19), dijkstra.distance(19, 19));
+        assert!(astar.explored < dijkstra.explored);
+    }
+
+    #[test]
+    fn test_astar_keeps_only_expanded_cells() {
+        let grid: Grid<char> = Grid::from_str(&vec![".".repeat(10); 10].join("\n")).unwrap();
+        let cost = |_: &Cell<char>, _: &Cell<char>| Some(1);
+        let astar = grid.astar(
+            0,
+            0,
+            &[(0, 5)],
+            Direction::cardinal(),
+            cost,
+            Point::manhattan,
+        );
+        assert_eq!(astar.distances.len(), astar.explored);
+        assert_eq!(astar.distance(0, 6), None);
+        let path = astar.path_to(&grid, 0, 5).unwrap();
+        assert_eq!(path.len(), 6);
+        for (key, distance) in astar.distances.iter() {
+            assert_eq!(*distance, Point::from(*key).manhattan(Point::default()));
+        }
+    }
+
+    #[test]
+    fn test_pose_search_prefers_fewer_turns() {
+        // Both routes are 4 steps, but the bottom route needs one turn instead of three
+        let grid: Grid<char> = Grid::from_str("...\n.#.\n...").unwrap();
+        let costs = TurnCosts {
+            forward: 1,
+            turn_left: 1000,
+            turn_right: 1000,
+        };
+        let start = Pose::new(0, 0, Direction::Down);
+        assert_eq!(start.point(), Point::default());
+        let result = grid.pose_search(start.clone(), &[(2, 2)], &costs, |cell| cell.value != '#');
+        assert_eq!(result.cost, Some(1004));
+        let at = grid.pose_search_at(start, &[Point::new(2, 2)], &costs, |cell| cell.value != '#');
+        assert_eq!(at.cells, result.cells);
+        assert_eq!(
+            result.cells,
+            HashSet::from([(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)])
+        );
+    }
+
+    #[test]
+    fn test_pose_search_collects_all_optimal_cells() {
+        // Facing right, going over or under the wall costs the same
+        let grid: Grid<char> = Grid::from_str("...\n.#.\n...").unwrap();
+        let costs = TurnCosts {
+            forward: 1,
+            turn_left: 1000,
+            turn_right: 1000,
+        };
+        let start = Pose::new(1, 0, Direction::Right);
+        let result = grid.pose_search(start, &[(1, 2)], &costs, |cell| cell.value != '#');
+        assert_eq!(result.cost, Some(3004));
+        assert_eq!(result.cells.len(), 8);
+        assert!(!result.cells.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_pose_search_unreachable() {
+        let grid: Grid<char> = Grid::from_str(".#.").unwrap();
+        let costs = TurnCosts {
+            forward: 1,
+            turn_left: 1,
+            turn_right: 1,
+        };
+        let start = Pose::new(0, 0, Direction::Right);
+        let result = grid.pose_search(start, &[(0, 2)], &costs, |cell| cell.value != '#');
+        assert_eq!(result.cost, None);
+        assert!(result.cells.is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::grid::Grid;
+
+// A small 2D template to search for in a Grid. None entries are wildcards that match anything
+#[derive(Clone, Debug)]
+pub struct Pattern<T: Clone> {
+    pub grid: Grid<Option<T>>,
+}
+
+impl<T: Clone + PartialEq> PartialEq for Pattern<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.grid.row_len == other.grid.row_len
+            && self.grid.col_len == other.grid.col_len
+            && self.grid.data == other.grid.data
+    }
+}
+
+impl<T: Clone> Pattern<T> {
+    // Returns None if there are no rows or the rows have different lengths
+    pub fn from_vecs(rows: &[Vec<Option<T>>]) -> Option<Self> {
+        let row_len = rows.first()?.len();
+        if row_len == 0 || rows.iter().any(|row| row.len() != row_len) {
+            return None;
+        }
+        Some(Self {
+            grid: Grid {
+                data: rows.iter().flat_map(|row| row.iter().cloned()).collect(),
+                row_len,
+                col_len: rows.len(),
+                wrapping: false,
+            },
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.col_len
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.row_len
+    }
+
+    // The pattern under all 4 rotations and their mirror images, without duplicates
+    pub fn orientations(&self) -> Vec<Pattern<T>>
+    where
+        T: PartialEq,
+    {
+        let mut orientations: Vec<Pattern<T>> = Vec::new();
+        let mut grid = self.grid.clone();
+        for _ in 0..4 {
+            for candidate in [grid.clone(), grid.flip_horizontal()] {
+                let candidate = Pattern { grid: candidate };
+                if !orientations.contains(&candidate) {
+                    orientations.push(candidate);
+                }
+            }
+            grid = grid.rotate_90();
+        }
+        orientations
+    }
+}
+
+impl Pattern<char> {
+    // Rows are separated by '/' or newlines and '.' is the wildcard, e.g. "M.S/.A./M.S"
+    pub fn parse(template: &str) -> Option<Self> {
+        Self::parse_with_wildcard(template, '.')
+    }
+
+    pub fn parse_with_wildcard(template: &str, wildcard: char) -> Option<Self> {
+        let rows: Vec<Vec<Option<char>>> = template
+            .trim()
+            .split(['/', '\n'])
+            .map(|line| {
+                line.trim()
+                    .chars()
+                    .map(|c| (c != wildcard).then_some(c))
+                    .collect()
+            })
+            .collect();
+        Self::from_vecs(&rows)
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    fn pattern_matches_at(&self, pattern: &Pattern<T>, y: usize, x: usize) -> bool {
+        for py in 0..pattern.height() {
+            for px in 0..pattern.width() {
+                let Some(expected) = &pattern.grid.data[py * pattern.width() + px] else {
+                    continue;
+                };
+                if self.data[(y + py) * self.row_len + x + px] != *expected {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // Top-left (y, x) of every place the pattern fits, in row-major order
+    pub fn find_pattern(&self, pattern: &Pattern<T>) -> Vec<(i32, i32)> {
+        if pattern.height() > self.col_len || pattern.width() > self.row_len {
+            return Vec::new();
+        }
+        let mut matches = Vec::new();
+        for y in 0..=(self.col_len - pattern.height()) {
+            for x in 0..=(self.row_len - pattern.width()) {
+                if self.pattern_matches_at(pattern, y, x) {
+                    matches.push((y as i32, x as i32));
+                }
+            }
+        }
+        matches
+    }
+
+    // Like find_pattern, trying every distinct rotation and reflection. The same (y, x) shows
+    // up more than once if different orientations match there
+    pub fn find_pattern_any_orientation(&self, pattern: &Pattern<T>) -> Vec<(i32, i32)> {
+        let mut matches: Vec<(i32, i32)> = pattern
+            .orientations()
+            .iter()
+            .flat_map(|orientation| self.find_pattern(orientation))
+            .collect();
+        matches.sort();
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pattern = Pattern::parse("M.S/.A./M.S").unwrap();
+        assert_eq!(pattern.height(), 3);
+        assert_eq!(pattern.width(), 3);
+        assert_eq!(pattern.grid.data[0], Some('M'));
+        assert_eq!(pattern.grid.data[1], None);
+
+        let pattern = Pattern::parse_with_wildcard("#?\n?#", '?').unwrap();
+        assert_eq!(pattern.grid.data, vec![Some('#'), None, None, Some('#')]);
+
+        assert!(Pattern::parse("ab/c").is_none());
+        assert!(Pattern::parse("").is_none());
+    }
+
+    #[test]
+    fn test_orientations() {
+        // Symmetric under every transform
+        assert_eq!(Pattern::parse("#").unwrap().orientations().len(), 1);
+        // Rotations differ, reflections repeat them
+        assert_eq!(
+            Pattern::parse("M.S/.A./M.S").unwrap().orientations().len(),
+            4
+        );
+        // An L shape is different in all 8 orientations
+        assert_eq!(Pattern::parse("#./#./##").unwrap().orientations().len(), 8);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let grid: Grid<char> = Grid::from_str("MXS\nXAX\nMXS\nXAX").unwrap();
+        let pattern = Pattern::parse("M.S/.A./M.S").unwrap();
+        assert_eq!(grid.find_pattern(&pattern), vec![(0, 0)]);
+
+        let too_big = Pattern::parse("...../.....").unwrap();
+        assert!(grid.find_pattern(&too_big).is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_any_orientation() {
+        let grid: Grid<char> = Grid::from_str("M.M.S\n.A.A.\nS.S.S").unwrap();
+        let pattern = Pattern::parse("M.S/.A./M.S").unwrap();
+        assert!(grid.find_pattern(&pattern).is_empty());
+        assert_eq!(grid.find_pattern_any_orientation(&pattern), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_find_pattern_numbers() {
+        let grid = Grid::from_vecs(&[vec![1, 2, 1], vec![2, 1, 2]]);
+        let pattern = Pattern::from_vecs(&[vec![Some(1), None], vec![None, Some(1)]]).unwrap();
+        assert_eq!(grid.find_pattern(&pattern), vec![(0, 0)]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::direction::Direction;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// A (y, x) coordinate or the offset between two coordinates
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Point {
+    pub y: i32,
+    pub x: i32,
+}
+
+impl Point {
+    pub fn new(y: i32, x: i32) -> Self {
+        Self { y, x }
+    }
+
+    // Admissible A* heuristic for Direction::cardinal() moves with unit costs
+    pub fn manhattan(&self, other: Point) -> u32 {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    // Admissible A* heuristic for Direction::all() moves with unit costs
+    pub fn chebyshev(&self, other: Point) -> u32 {
+        self.y.abs_diff(other.y).max(self.x.abs_diff(other.x))
+    }
+
+    // One step from here in the given direction
+    pub fn step(&self, direction: &Direction) -> Point {
+        *self + Point::from(direction)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.y + other.y, self.x + other.x)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.y - other.y, self.x - other.x)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i32) -> Point {
+        Point::new(self.y * scale, self.x * scale)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.y, -self.x)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((y, x): (i32, i32)) -> Self {
+        Point::new(y, x)
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(point: Point) -> Self {
+        (point.y, point.x)
+    }
+}
+
+impl From<&Direction> for Point {
+    fn from(direction: &Direction) -> Self {
+        Point::from(direction.delta())
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(direction: Direction) -> Self {
+        Point::from(&direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -3);
+        assert_eq!(a + b, Point::new(5, -1));
+        assert_eq!(b - a, Point::new(3, -5));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(-a, Point::new(-1, -2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(0, 0);
+        let b = Point::new(3, -4);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn test_from_direction() {
+        assert_eq!(Point::from(Direction::Up), Point::new(-1, 0));
+        assert_eq!(Point::from(&Direction::DiagonalDownLeft), Point::new(1, -1));
+        assert_eq!(Point::new(2, 2).step(&Direction::Right), Point::new(2, 3));
+        assert_eq!(<(i32, i32)>::from(Point::new(5, 6)), (5, 6));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{cell::Cell, direction::Direction, grid::Grid};
+
+// Where cast_until stopped. last is the final cell the ray passed through before stopping,
+// which is the start cell if the ray stopped on its first step
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum RayStop<T: Clone> {
+    // The ray reached a cell matching the predicate
+    Hit { cell: Cell<T>, last: Cell<T> },
+    // The ray left the grid, or came back around to the start on a wrapping grid
+    Exit { last: Cell<T> },
+}
+
+impl<T: Clone> Grid<T> {
+    // Cells from (y, x) in a straight line, not including (y, x) itself, until the edge.
+    // On a wrapping grid the ray ends just before it would come back to the start
+    pub fn ray(&self, y: i32, x: i32, direction: Direction) -> impl Iterator<Item = Cell<T>> + '_ {
+        let (y_step, x_step) = direction.delta();
+        let start = self.get_cell(y, x).map(|cell| (cell.y, cell.x));
+        (1..)
+            .map_while(move |i| {
+                start?;
+                self.get_cell(y + i * y_step, x + i * x_step)
+            })
+            .take_while(move |cell| Some((cell.y, cell.x)) != start)
+    }
+
+    // Walk the ray until is_stop matches a cell. None if (y, x) is not in the grid
+    pub fn cast_until<F>(
+        &self,
+        y: i32,
+        x: i32,
+        direction: Direction,
+        is_stop: F,
+    ) -> Option<RayStop<T>>
+    where
+        F: Fn(&Cell<T>) -> bool,
+    {
+        self.walk_until(y, x, direction, is_stop, |_| {})
+    }
+
+    // cast_until that also returns the cells passed through on the way, in order and not
+    // including (y, x) or the cell that stopped the ray
+    pub fn trace_until<F>(
+        &self,
+        y: i32,
+        x: i32,
+        direction: Direction,
+        is_stop: F,
+    ) -> Option<(Vec<Cell<T>>, RayStop<T>)>
+    where
+        F: Fn(&Cell<T>) -> bool,
+    {
+        let mut path = Vec::new();
+        let stop = self.walk_until(y, x, direction, is_stop, |cell| path.push(cell.clone()))?;
+        Some((path, stop))
+    }
+
+    fn walk_until<F, V>(
+        &self,
+        y: i32,
+        x: i32,
+        direction: Direction,
+        is_stop: F,
+        mut visit: V,
+    ) -> Option<RayStop<T>>
+    where
+        F: Fn(&Cell<T>) -> bool,
+        V: FnMut(&Cell<T>),
+    {
+        let mut last = self.get_cell(y, x)?;
+        for cell in self.ray(y, x, direction) {
+            if is_stop(&cell) {
+                return Some(RayStop::Hit { cell, last });
+            }
+            visit(&cell);
+            last = cell;
+        }
+        Some(RayStop::Exit { last })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_room() -> Grid<char> {
+        Grid::from_str("..#.\n....\n.^..\n...#").unwrap()
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = make_room();
+        let values: Vec<char> = grid.ray(2, 1, Direction::Right).map(|c| c.value).collect();
+        assert_eq!(values, vec!['.', '.']);
+        assert_eq!(grid.ray(0, 0, Direction::Up).count(), 0);
+        assert_eq!(grid.ray(-1, 0, Direction::Down).count(), 0);
+
+        let diagonal: Vec<Cell<char>> = grid.ray(0, 0, Direction::DiagonalDownRight).collect();
+        assert_eq!(diagonal.last(), Some(&Cell::new('#', 3, 3)));
+    }
+
+    #[test]
+    fn test_ray_is_lazy() {
+        let grid = make_room().with_wrapping(true);
+        // The ray would go around forever, but it stops before repeating the start
+        assert_eq!(grid.ray(1, 0, Direction::Right).count(), 3);
+        assert_eq!(grid.ray(1, 0, Direction::Right).take(2).count(), 2);
+    }
+
+    #[test]
+    fn test_cast_until_hit() {
+        let grid = make_room();
+        let stop = grid.cast_until(2, 2, Direction::Up, |c| c.value == '#');
+        assert_eq!(
+            stop,
+            Some(RayStop::Hit {
+                cell: Cell::new('#', 0, 2),
+                last: Cell::new('.', 1, 2),
+            })
+        );
+    }
+
+    #[test]
+    fn test_cast_until_exit() {
+        let grid = make_room();
+        let stop = grid.cast_until(2, 1, Direction::Left, |c| c.value == '#');
+        assert_eq!(
+            stop,
+            Some(RayStop::Exit {
+                last: Cell::new('.', 2, 0)
+            })
+        );
+        let stop = grid.cast_until(2, 0, Direction::Left, |c| c.value == '#');
+        assert_eq!(
+            stop,
+            Some(RayStop::Exit {
+                last: Cell::new('.', 2, 0)
+            })
+        );
+        assert_eq!(grid.cast_until(9, 9, Direction::Left, |_| true), None);
+    }
+
+    #[test]
+    fn test_trace_until() {
+        let grid = make_room();
+        let (path, stop) = grid
+            .trace_until(3, 2, Direction::Up, |c| c.value == '#')
+            .unwrap();
+        let points: Vec<(i32, i32)> = path.iter().map(|c| (c.y, c.x)).collect();
+        assert_eq!(points, vec![(2, 2), (1, 2)]);
+        assert!(matches!(stop, RayStop::Hit { last, .. } if last == Cell::new('.', 1, 2)));
+
+        let (path, stop) = grid.trace_until(1, 1, Direction::Left, |_| false).unwrap();
+        assert_eq!(path, vec![Cell::new('.', 1, 0)]);
+        assert_eq!(
+            stop,
+            RayStop::Exit {
+                last: Cell::new('.', 1, 0)
+            }
+        );
+    }
+}
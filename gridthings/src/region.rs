@@ -0,0 +1,301 @@
+use crate::{
+    cell::Cell,
+    direction::{Direction, DirectionSet},
+    grid::Grid,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// A group of connected cells. bounds is (min_y, min_x, max_y, max_x), inclusive
+#[derive(Clone, Debug)]
+pub struct Region<T: Clone> {
+    pub id: usize,
+    pub cells: Vec<Cell<T>>,
+    pub bounds: (i32, i32, i32, i32),
+}
+
+impl<T: Clone> Region<T> {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn height(&self) -> usize {
+        (self.bounds.2 - self.bounds.0 + 1) as usize
+    }
+
+    pub fn width(&self) -> usize {
+        (self.bounds.3 - self.bounds.1 + 1) as usize
+    }
+
+    pub fn contains(&self, y: i32, x: i32) -> bool {
+        self.cells.iter().any(|cell| cell.y == y && cell.x == x)
+    }
+
+    fn positions(&self) -> HashSet<(i32, i32)> {
+        self.cells.iter().map(|cell| (cell.y, cell.x)).collect()
+    }
+
+    // Number of cell edges that face a cell outside the region, holes included
+    pub fn perimeter(&self) -> usize {
+        let positions = self.positions();
+        let mut perimeter = 0;
+        for cell in &self.cells {
+            for direction in Direction::cardinal() {
+                let (dy, dx) = direction.delta();
+                if !positions.contains(&(cell.y + dy, cell.x + dx)) {
+                    perimeter += 1;
+                }
+            }
+        }
+        perimeter
+    }
+
+    // Number of straight sides. A closed outline has as many sides as corners, so count
+    // the outer and inner corners of every cell
+    pub fn sides(&self) -> usize {
+        let positions = self.positions();
+        let mut corners = 0;
+        for cell in &self.cells {
+            for first in Direction::cardinal() {
+                let second = first.turn_right();
+                let (y1, x1) = first.delta();
+                let (y2, x2) = second.delta();
+                let has_first = positions.contains(&(cell.y + y1, cell.x + x1));
+                let has_second = positions.contains(&(cell.y + y2, cell.x + x2));
+                let has_diagonal = positions.contains(&(cell.y + y1 + y2, cell.x + x1 + x2));
+                let is_outer = !has_first && !has_second;
+                let is_inner = has_first && has_second && !has_diagonal;
+                if is_outer || is_inner {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+
+    // Ordered outlines of the region as (cell, side) pairs, where side is the Direction the
+    // edge faces out of the cell. The outer outline comes first and runs clockwise, any
+    // holes follow and run counter-clockwise
+    pub fn boundary(&self) -> Vec<Vec<(Cell<T>, Direction)>> {
+        let positions = self.positions();
+        let by_position: HashMap<(i32, i32), &Cell<T>> = self
+            .cells
+            .iter()
+            .map(|cell| ((cell.y, cell.x), cell))
+            .collect();
+        let mut remaining = BTreeSet::new();
+        for cell in &self.cells {
+            for direction in Direction::cardinal() {
+                let (dy, dx) = direction.delta();
+                if !positions.contains(&(cell.y + dy, cell.x + dx)) {
+                    remaining.insert((cell.y, cell.x, direction));
+                }
+            }
+        }
+
+        let mut outlines = Vec::new();
+        // The top edge of the top-left cell is always on the outer outline
+        let mut start = remaining
+            .iter()
+            .find(|(_, _, d)| *d == Direction::Up)
+            .cloned();
+        while let Some((start_y, start_x, start_side)) = start {
+            let mut outline = Vec::new();
+            let (mut y, mut x, mut side) = (start_y, start_x, start_side.clone());
+            loop {
+                remaining.remove(&(y, x, side.clone()));
+                outline.push((by_position[&(y, x)].clone(), side.clone()));
+
+                // Walk along the edge with the region on the right hand side
+                let travel = side.turn_right();
+                let (ty, tx) = travel.delta();
+                let (sy, sx) = side.delta();
+                let ahead = (y + ty, x + tx);
+                let diagonal = (y + ty + sy, x + tx + sx);
+                if !positions.contains(&ahead) {
+                    side = travel;
+                } else if positions.contains(&diagonal) {
+                    (y, x) = diagonal;
+                    side = side.turn_left();
+                } else {
+                    (y, x) = ahead;
+                }
+
+                if (y, x, &side) == (start_y, start_x, &start_side) {
+                    break;
+                }
+            }
+            outlines.push(outline);
+            start = remaining.iter().next().cloned();
+        }
+        outlines
+    }
+}
+
+// Every region in a grid plus a same-shaped grid holding the region id of each cell
+#[derive(Clone, Debug)]
+pub struct Regions<T: Clone> {
+    pub regions: Vec<Region<T>>,
+    pub labels: Grid<usize>,
+}
+
+impl<T: Clone> Grid<T> {
+    // Label connected components. Two neighboring cells join the same region when
+    // is_same(a, b) is true, neighbors are looked up in the given directions
+    pub fn regions<D, F>(&self, directions: D, is_same: F) -> Regions<T>
+    where
+        D: Into<DirectionSet>,
+        F: Fn(&Cell<T>, &Cell<T>) -> bool,
+    {
+        let directions = directions.into();
+        let mut labels: Vec<Option<usize>> = vec![None; self.data.len()];
+        let mut regions = Vec::new();
+
+        for cell in self.iter_cells() {
+            let idx = cell.y as usize * self.row_len + cell.x as usize;
+            if labels[idx].is_some() {
+                continue;
+            }
+
+            let id = regions.len();
+            labels[idx] = Some(id);
+            let mut bounds = (cell.y, cell.x, cell.y, cell.x);
+            let mut cells = Vec::new();
+            let mut stack = vec![cell];
+            while let Some(current) = stack.pop() {
+                bounds.0 = bounds.0.min(current.y);
+                bounds.1 = bounds.1.min(current.x);
+                bounds.2 = bounds.2.max(current.y);
+                bounds.3 = bounds.3.max(current.x);
+                for neighbor in self.get_cell_neighbors(current.y, current.x, directions) {
+                    let neighbor_idx = neighbor.y as usize * self.row_len + neighbor.x as usize;
+                    if labels[neighbor_idx].is_some() || !is_same(&current, &neighbor) {
+                        continue;
+                    }
+                    labels[neighbor_idx] = Some(id);
+                    stack.push(neighbor);
+                }
+                cells.push(current);
+            }
+            cells.sort_by_key(|cell| (cell.y, cell.x));
+            regions.push(Region { id, cells, bounds });
+        }
+
+        let labels = Grid {
+            data: labels.into_iter().map(|label| label.unwrap()).collect(),
+            row_len: self.row_len,
+            col_len: self.col_len,
+            wrapping: self.wrapping,
+        };
+        Regions { regions, labels }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_garden() -> Grid<char> {
+        Grid::from_str("AAAA\nBBCD\nBBCC\nEEEC").unwrap()
+    }
+
+    #[test]
+    fn test_regions_equal_values() {
+        let grid = make_garden();
+        let result = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        let areas: Vec<(char, usize)> = result
+            .regions
+            .iter()
+            .map(|region| (region.cells[0].value, region.area()))
+            .collect();
+        assert_eq!(
+            areas,
+            vec![('A', 4), ('B', 4), ('C', 4), ('D', 1), ('E', 3)]
+        );
+    }
+
+    #[test]
+    fn test_region_bounds() {
+        let grid = make_garden();
+        let result = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        let c_region = &result.regions[2];
+        assert_eq!(c_region.bounds, (1, 2, 3, 3));
+        assert_eq!(c_region.height(), 3);
+        assert_eq!(c_region.width(), 2);
+    }
+
+    #[test]
+    fn test_region_labels() {
+        let grid = make_garden();
+        let result = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        assert_eq!(result.labels.row_len, 4);
+        assert_eq!(result.labels.col_len, 4);
+        assert_eq!(
+            result.labels.data,
+            vec![0, 0, 0, 0, 1, 1, 2, 3, 1, 1, 2, 2, 4, 4, 4, 2]
+        );
+    }
+
+    #[test]
+    fn test_regions_disconnected_same_value() {
+        // Diagonal neighbors only join when diagonal directions are searched
+        let grid: Grid<char> = Grid::from_str("X.\n.X").unwrap();
+        let cardinal = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        assert_eq!(cardinal.regions.len(), 4);
+        let all = grid.regions(Direction::all(), |a, b| a.value == b.value);
+        assert_eq!(all.regions.len(), 2);
+    }
+
+    #[test]
+    fn test_region_perimeter_and_sides() {
+        let grid = make_garden();
+        let result = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        let geometry: Vec<(usize, usize)> = result
+            .regions
+            .iter()
+            .map(|region| (region.perimeter(), region.sides()))
+            .collect();
+        assert_eq!(geometry, vec![(10, 4), (8, 4), (10, 8), (4, 4), (8, 4)]);
+    }
+
+    #[test]
+    fn test_region_with_holes() {
+        let grid: Grid<char> = Grid::from_str("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").unwrap();
+        let result = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        let outer = &result.regions[0];
+        assert_eq!(outer.area(), 21);
+        assert_eq!(outer.perimeter(), 36);
+        assert_eq!(outer.sides(), 20);
+
+        let outlines = outer.boundary();
+        assert_eq!(outlines.len(), 5);
+        assert_eq!(outlines[0].len(), 20);
+        assert!(outlines[1..].iter().all(|outline| outline.len() == 4));
+        let edge_count: usize = outlines.iter().map(|outline| outline.len()).sum();
+        assert_eq!(edge_count, outer.perimeter());
+    }
+
+    #[test]
+    fn test_region_boundary_order() {
+        let grid: Grid<char> = Grid::from_str("AA\nA.").unwrap();
+        let result = grid.regions(Direction::cardinal(), |a, b| a.value == b.value);
+        let outlines = result.regions[0].boundary();
+        assert_eq!(outlines.len(), 1);
+        let edges: Vec<(i32, i32, Direction)> = outlines[0]
+            .iter()
+            .map(|(cell, side)| (cell.y, cell.x, side.clone()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                (0, 0, Direction::Up),
+                (0, 1, Direction::Up),
+                (0, 1, Direction::Right),
+                (0, 1, Direction::Down),
+                (1, 0, Direction::Right),
+                (1, 0, Direction::Down),
+                (1, 0, Direction::Left),
+                (0, 0, Direction::Left),
+            ]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{cell::Cell, grid::Grid};
+use std::{collections::HashSet, fmt::Display};
+
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    // ANSI foreground code, background is the same code plus 10
+    fn code(&self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub fn new(fg: Option<Color>, bg: Option<Color>, bold: bool) -> Self {
+        Self { fg, bg, bold }
+    }
+
+    pub fn fg(color: Color) -> Self {
+        Self::new(Some(color), None, false)
+    }
+
+    pub fn bg(color: Color) -> Self {
+        Self::new(None, Some(color), false)
+    }
+
+    pub fn bold() -> Self {
+        Self::new(None, None, true)
+    }
+
+    // Fields set on other win over fields set on self
+    pub fn merge(&self, other: &Style) -> Style {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: self.bold || other.bold,
+        }
+    }
+
+    fn paint(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if let Some(fg) = self.fg {
+            codes.push(fg.code().to_string());
+        }
+        if let Some(bg) = self.bg {
+            codes.push((bg.code() + 10).to_string());
+        }
+        if codes.is_empty() {
+            return text.to_string();
+        }
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+// Controls for Grid::render. highlights is an overlay of (y, x) positions such as a path,
+// drawn with highlight_style and, if set, highlight_char in place of the cell value
+#[derive(Clone, Debug)]
+pub struct RenderOptions {
+    pub color: bool,
+    pub rulers: bool,
+    pub highlights: HashSet<(i32, i32)>,
+    pub highlight_style: Style,
+    pub highlight_char: Option<char>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::color()
+    }
+}
+
+impl RenderOptions {
+    // No escape codes, for logs and tests
+    pub fn plain() -> Self {
+        Self {
+            color: false,
+            rulers: false,
+            highlights: HashSet::new(),
+            highlight_style: Style::default(),
+            highlight_char: None,
+        }
+    }
+
+    pub fn color() -> Self {
+        Self {
+            color: true,
+            highlight_style: Style::new(None, Some(Color::Yellow), true),
+            ..Self::plain()
+        }
+    }
+}
+
+impl<T: Clone + Display> Grid<T> {
+    // Render the grid as lines of text, styling each cell with style(cell). Cells wider
+    // than one character are right aligned and separated by a space
+    pub fn render<F>(&self, options: &RenderOptions, style: F) -> String
+    where
+        F: Fn(&Cell<T>) -> Style,
+    {
+        let width = self
+            .data
+            .iter()
+            .map(|value| value.to_string().chars().count())
+            .max()
+            .unwrap_or(1);
+        let separator = if width > 1 { " " } else { "" };
+        let ruler_width = self.col_len.saturating_sub(1).to_string().len();
+
+        let mut lines = Vec::new();
+        if options.rulers {
+            let modulus = 10_usize.pow(width.min(9) as u32);
+            let labels: Vec<String> = (0..self.row_len)
+                .map(|x| format!("{:>width$}", x % modulus))
+                .collect();
+            lines.push(format!("{:ruler_width$} {}", "", labels.join(separator)));
+        }
+
+        for row in self.iter_rows() {
+            let mut parts = Vec::new();
+            for cell in row.iter() {
+                let is_highlighted = options.highlights.contains(&(cell.y, cell.x));
+                let text = match options.highlight_char {
+                    Some(c) if is_highlighted => format!("{:>width$}", c),
+                    _ => format!("{:>width$}", cell.value),
+                };
+                if !options.color {
+                    parts.push(text);
+                    continue;
+                }
+                let mut cell_style = style(cell);
+                if is_highlighted {
+                    cell_style = cell_style.merge(&options.highlight_style);
+                }
+                parts.push(cell_style.paint(&text));
+            }
+            let line = parts.join(separator);
+            if options.rulers {
+                lines.push(format!("{:>ruler_width$} {}", row[0].y, line));
+            } else {
+                lines.push(line);
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_char_grid() -> Grid<char> {
+        Grid::from_str("#..\n.#.\n..#").unwrap()
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let grid = make_char_grid();
+        let output = grid.render(&RenderOptions::plain(), |_| Style::bold());
+        assert_eq!(output, "#..\n.#.\n..#");
+    }
+
+    #[test]
+    fn test_render_rulers() {
+        let grid: Grid<char> = Grid::from_str(&vec![".".repeat(12); 11].join("\n")).unwrap();
+        let options = RenderOptions {
+            rulers: true,
+            ..RenderOptions::plain()
+        };
+        let output = grid.render(&options, |_| Style::default());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "   012345678901");
+        assert_eq!(lines[1], " 0 ............");
+        assert_eq!(lines[11], "10 ............");
+    }
+
+    #[test]
+    fn test_render_wide_values() {
+        let grid = Grid::from_vecs(&[vec![1, 20], vec![300, 4]]);
+        let options = RenderOptions {
+            rulers: true,
+            ..RenderOptions::plain()
+        };
+        let output = grid.render(&options, |_| Style::default());
+        assert_eq!(output, "    0   1\n0   1  20\n1 300   4");
+    }
+
+    #[test]
+    fn test_render_highlight_char() {
+        let grid = make_char_grid();
+        let options = RenderOptions {
+            highlights: HashSet::from([(0, 1), (0, 2)]),
+            highlight_char: Some('O'),
+            ..RenderOptions::plain()
+        };
+        let output = grid.render(&options, |_| Style::default());
+        assert_eq!(output, "#OO\n.#.\n..#");
+    }
+
+    #[test]
+    fn test_render_color() {
+        let grid = make_char_grid();
+        let options = RenderOptions {
+            highlights: HashSet::from([(0, 1)]),
+            ..RenderOptions::color()
+        };
+        let output = grid.render(&options, |cell| match cell.value {
+            '#' => Style::fg(Color::Red),
+            _ => Style::default(),
+        });
+        let first_line = output.lines().next().unwrap();
+        assert_eq!(first_line, "\x1b[31m#\x1b[0m\x1b[1;43m.\x1b[0m.");
+    }
+
+    #[test]
+    fn test_style_merge() {
+        let base = Style::new(Some(Color::Red), Some(Color::Black), false);
+        let merged = base.merge(&Style::bg(Color::Blue));
+        assert_eq!(
+            merged,
+            Style::new(Some(Color::Red), Some(Color::Blue), false)
+        );
+    }
+}
@@ -1,13 +1,13 @@
 use crate::{cell::Cell, direction::Direction};
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
-pub struct Segment<T: Copy> {
+pub struct Segment<T: Clone> {
     pub values: Vec<T>,
     pub cells: Vec<Cell<T>>,
     pub direction: Direction,
 }
 
-impl<T: Copy> Segment<T> {
+impl<T: Clone> Segment<T> {
     pub fn new(values: Vec<T>, cells: Vec<Cell<T>>, direction: Direction) -> Self {
         Self {
             values,
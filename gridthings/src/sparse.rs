@@ -0,0 +1,355 @@
+use crate::{
+    direction::{Direction, DirectionSet, IntoDirections},
+    grid::{Grid, GridError},
+};
+use std::collections::HashMap;
+
+// Like Cell, but with i64 coordinates that can be negative or very large
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct SparseCell<T: Clone> {
+    pub value: T,
+    pub y: i64,
+    pub x: i64,
+}
+
+impl<T: Clone> SparseCell<T> {
+    pub fn new(value: T, y: i64, x: i64) -> Self {
+        Self { value, y, x }
+    }
+}
+
+// A straight run of stored cells, like Segment for a SparseGrid
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct SparseSegment<T: Clone> {
+    pub values: Vec<T>,
+    pub cells: Vec<SparseCell<T>>,
+    pub direction: Direction,
+}
+
+impl<T: Clone> SparseSegment<T> {
+    pub fn start(&self) -> Option<&SparseCell<T>> {
+        self.cells.first()
+    }
+
+    pub fn end(&self) -> Option<&SparseCell<T>> {
+        self.cells.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+// A grid that only stores the cells that have been set. bounds is (min_y, min_x, max_y, max_x)
+// of the stored cells and grows as cells are added. extent is the area of the dense grid it
+// was made from, which bounds never shrinks below so fill borders survive a round trip
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T: Clone> {
+    data: HashMap<(i64, i64), T>,
+    bounds: Option<(i64, i64, i64, i64)>,
+    extent: Option<(i64, i64, i64, i64)>,
+}
+
+impl<T: Clone> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            data: HashMap::new(),
+            bounds: None,
+            extent: None,
+        }
+    }
+
+    // Every cell of the dense grid except the ones equal to fill. The grid's extent is kept,
+    // so to_grid gives back the same grid even if its border is all fill
+    pub fn from_grid(grid: &Grid<T>, fill: T) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut sparse = Self::new();
+        if !grid.data.is_empty() {
+            sparse.extent = Some((0, 0, grid.col_len as i64 - 1, grid.row_len as i64 - 1));
+            sparse.bounds = sparse.extent;
+        }
+        for (idx, value) in grid.data.iter().enumerate() {
+            if *value == fill {
+                continue;
+            }
+            let y = (idx / grid.row_len) as i64;
+            let x = (idx % grid.row_len) as i64;
+            sparse.set_value(y, x, value.clone());
+        }
+        sparse
+    }
+
+    // Dense copy of the area inside bounds() with missing cells set to fill.
+    // (0, 0) in the returned grid is (min_y, min_x) here. Empty if nothing is stored, and
+    // TooLarge if the area has more cells than fit in memory
+    pub fn to_grid(&self, fill: T) -> Result<Grid<T>, GridError> {
+        let (min_y, min_x, max_y, max_x) = self.bounds.ok_or(GridError::Empty)?;
+        let span = |min: i64, max: i64| {
+            let len = max.checked_sub(min)?.checked_add(1)?;
+            usize::try_from(len).ok()
+        };
+        let row_len = span(min_x, max_x).ok_or(GridError::TooLarge)?;
+        let col_len = span(min_y, max_y).ok_or(GridError::TooLarge)?;
+        let size = row_len.checked_mul(col_len).ok_or(GridError::TooLarge)?;
+        let mut data = Vec::new();
+        data.try_reserve_exact(size)
+            .map_err(|_| GridError::TooLarge)?;
+        data.resize(size, fill);
+        for (&(y, x), value) in self.data.iter() {
+            data[(y - min_y) as usize * row_len + (x - min_x) as usize] = value.clone();
+        }
+        Ok(Grid {
+            data,
+            row_len,
+            col_len,
+            wrapping: false,
+        })
+    }
+
+    pub fn bounds(&self) -> Option<(i64, i64, i64, i64)> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, y: i64, x: i64) -> Option<&T> {
+        self.data.get(&(y, x))
+    }
+
+    pub fn get_value(&self, y: i64, x: i64) -> Option<T> {
+        self.get(y, x).cloned()
+    }
+
+    // Stored (y, x) and values in no particular order, see iter_cells for row-major order
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> + '_ {
+        self.data.iter().map(|(&key, value)| (key, value))
+    }
+
+    pub fn set_value(&mut self, y: i64, x: i64, value: T) {
+        self.data.insert((y, x), value);
+        self.expand_bounds(y, x);
+    }
+
+    fn expand_bounds(&mut self, y: i64, x: i64) {
+        self.bounds = Some(match self.bounds {
+            None => (y, x, y, x),
+            Some((min_y, min_x, max_y, max_x)) => {
+                (min_y.min(y), min_x.min(x), max_y.max(y), max_x.max(x))
+            }
+        });
+    }
+
+    pub fn remove(&mut self, y: i64, x: i64) -> Option<T> {
+        let value = self.data.remove(&(y, x))?;
+        let (min_y, min_x, max_y, max_x) = self.bounds.unwrap();
+        if y == min_y || y == max_y || x == min_x || x == max_x {
+            self.recompute_bounds();
+        }
+        Some(value)
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = self.extent;
+        let keys: Vec<(i64, i64)> = self.data.keys().copied().collect();
+        for (y, x) in keys {
+            self.expand_bounds(y, x);
+        }
+    }
+
+    pub fn get_cell(&self, y: i64, x: i64) -> Option<SparseCell<T>> {
+        self.get_value(y, x)
+            .map(|value| SparseCell::new(value, y, x))
+    }
+
+    // Stored cells in row-major order
+    pub fn iter_cells(&self) -> impl Iterator<Item = SparseCell<T>> + '_ {
+        let mut keys: Vec<&(i64, i64)> = self.data.keys().collect();
+        keys.sort();
+        keys.into_iter()
+            .map(move |&(y, x)| SparseCell::new(self.data[&(y, x)].clone(), y, x))
+    }
+
+    // Stored cells grouped by row, top to bottom. Rows with no stored cells are skipped
+    pub fn iter_rows(&self) -> impl Iterator<Item = Vec<SparseCell<T>>> + '_ {
+        let mut rows: Vec<Vec<SparseCell<T>>> = Vec::new();
+        for cell in self.iter_cells() {
+            match rows.last_mut() {
+                Some(row) if row[0].y == cell.y => row.push(cell),
+                _ => rows.push(vec![cell]),
+            }
+        }
+        rows.into_iter()
+    }
+
+    pub fn get_cell_neighbor(&self, y: i64, x: i64, direction: Direction) -> Option<SparseCell<T>> {
+        let (y_step, x_step) = direction.delta();
+        self.get_cell(y + y_step as i64, x + x_step as i64)
+    }
+
+    pub fn get_cell_neighbors<D: IntoDirections>(
+        &self,
+        y: i64,
+        x: i64,
+        directions: D,
+    ) -> Vec<SparseCell<T>> {
+        directions
+            .into_directions()
+            .filter_map(|direction| self.get_cell_neighbor(y, x, direction))
+            .collect()
+    }
+
+    // Coordinates of every neighbor whether or not it is stored, for growing into empty space
+    pub fn neighbor_coords<D: Into<DirectionSet>>(
+        &self,
+        y: i64,
+        x: i64,
+        directions: D,
+    ) -> Vec<(i64, i64)> {
+        directions
+            .into()
+            .iter()
+            .map(|direction| {
+                let (y_step, x_step) = direction.delta();
+                (y + y_step as i64, x + x_step as i64)
+            })
+            .collect()
+    }
+
+    // Every cell along the segment must be stored, otherwise None
+    pub fn get_segment(
+        &self,
+        y_start: i64,
+        x_start: i64,
+        direction: Direction,
+        length: usize,
+    ) -> Option<SparseSegment<T>> {
+        let (y_step, x_step) = direction.delta();
+        let cells = (0..length as i64)
+            .map(|i| self.get_cell(y_start + i * y_step as i64, x_start + i * x_step as i64))
+            .collect::<Option<Vec<_>>>()?;
+        Some(SparseSegment {
+            values: cells.iter().map(|cell| cell.value.clone()).collect(),
+            cells,
+            direction,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.set_value(0, 0, 'a');
+        grid.set_value(-5, 10_000_000_000, 'b');
+        assert_eq!(grid.bounds(), Some((-5, 0, 0, 10_000_000_000)));
+        assert_eq!(grid.get_value(-5, 10_000_000_000), Some('b'));
+        assert_eq!(grid.get_value(1, 1), None);
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_shrinks_bounds() {
+        let mut grid = SparseGrid::new();
+        grid.set_value(0, 0, 1);
+        grid.set_value(3, 4, 2);
+        assert_eq!(grid.remove(3, 4), Some(2));
+        assert_eq!(grid.bounds(), Some((0, 0, 0, 0)));
+        assert_eq!(grid.remove(3, 4), None);
+        grid.remove(0, 0);
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut grid = SparseGrid::new();
+        grid.set_value(-1, -1, 1);
+        grid.set_value(-1, 0, 2);
+        assert_eq!(
+            grid.get_cell_neighbors(0, 0, Direction::all()),
+            vec![SparseCell::new(1, -1, -1), SparseCell::new(2, -1, 0)]
+        );
+        assert_eq!(grid.neighbor_coords(0, 0, Direction::all()).len(), 8);
+    }
+
+    #[test]
+    fn test_segment_and_iteration() {
+        let mut grid = SparseGrid::new();
+        for x in -2..2 {
+            grid.set_value(7, x, x);
+        }
+        grid.set_value(-3, 5, 9);
+        let segment = grid.get_segment(7, -2, Direction::Right, 4).unwrap();
+        assert_eq!(segment.values, vec![-2, -1, 0, 1]);
+        assert_eq!(segment.direction, Direction::Right);
+        assert_eq!(segment.end(), Some(&SparseCell::new(1, 7, 1)));
+        assert_eq!(grid.get_segment(7, -2, Direction::Right, 5), None);
+
+        let xs: Vec<i64> = grid.iter_cells().map(|cell| cell.x).collect();
+        assert_eq!(xs, vec![5, -2, -1, 0, 1]);
+        let rows: Vec<usize> = grid.iter_rows().map(|row| row.len()).collect();
+        assert_eq!(rows, vec![1, 4]);
+        assert_eq!(grid.get(-3, 5), Some(&9));
+        assert_eq!(grid.iter().count(), 5);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let dense: Grid<char> = Grid::from_str("#..\n.#.\n..#").unwrap();
+        let sparse = SparseGrid::from_grid(&dense, '.');
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse.to_grid('.').unwrap().data, dense.data);
+
+        let mut sparse = sparse;
+        sparse.set_value(-1, -1, '#');
+        let grown = sparse.to_grid('.').unwrap();
+        assert_eq!(grown.to_string(), "#...\n.#..\n..#.\n...#");
+    }
+
+    #[test]
+    fn test_to_grid_errors() {
+        let empty: SparseGrid<char> = SparseGrid::new();
+        assert!(matches!(empty.to_grid('.'), Err(GridError::Empty)));
+
+        let mut wide = SparseGrid::new();
+        wide.set_value(0, 0, '#');
+        wide.set_value(0, i64::MAX, '#');
+        assert!(matches!(wide.to_grid('.'), Err(GridError::TooLarge)));
+        wide.set_value(0, i64::MIN, '#');
+        assert!(matches!(wide.to_grid('.'), Err(GridError::TooLarge)));
+    }
+
+    #[test]
+    fn test_round_trip_keeps_fill_border() {
+        let dense: Grid<char> = Grid::from_str("..\n.#").unwrap();
+        let mut sparse = SparseGrid::from_grid(&dense, '.');
+        assert_eq!(sparse.bounds(), Some((0, 0, 1, 1)));
+        assert_eq!(sparse.to_grid('.').unwrap().to_string(), "..\n.#");
+
+        // Removing the only stored cell does not shrink the grid it came from
+        sparse.remove(1, 1);
+        assert_eq!(sparse.to_grid('.').unwrap().to_string(), "..\n..");
+    }
+}
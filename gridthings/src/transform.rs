@@ -0,0 +1,212 @@
+use crate::grid::Grid;
+
+impl<T: Clone> Grid<T> {
+    // New grid of the given shape that keeps the wrapping mode of self
+    fn reshaped<F>(&self, row_len: usize, col_len: usize, value: F) -> Grid<T>
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        let mut data = Vec::with_capacity(row_len * col_len);
+        for y in 0..col_len {
+            for x in 0..row_len {
+                data.push(value(y, x));
+            }
+        }
+        Grid {
+            data,
+            row_len,
+            col_len,
+            wrapping: self.wrapping,
+        }
+    }
+
+    fn raw_value(&self, y: usize, x: usize) -> T {
+        self.data[y * self.row_len + x].clone()
+    }
+
+    fn is_square(&self) -> bool {
+        self.row_len == self.col_len
+    }
+
+    // Swap rows and columns, (y, x) moves to (x, y)
+    pub fn transpose(&self) -> Grid<T> {
+        self.reshaped(self.col_len, self.row_len, |y, x| self.raw_value(x, y))
+    }
+
+    // Rotate 90 degrees clockwise, the first column becomes the first row reversed
+    pub fn rotate_90(&self) -> Grid<T> {
+        self.reshaped(self.col_len, self.row_len, |y, x| {
+            self.raw_value(self.col_len - 1 - x, y)
+        })
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        let mut grid = self.clone();
+        grid.rotate_180_in_place();
+        grid
+    }
+
+    // Rotate 270 degrees clockwise, same as 90 degrees counter-clockwise
+    pub fn rotate_270(&self) -> Grid<T> {
+        self.reshaped(self.col_len, self.row_len, |y, x| {
+            self.raw_value(x, self.row_len - 1 - y)
+        })
+    }
+
+    // Mirror left to right, each row is reversed
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let mut grid = self.clone();
+        grid.flip_horizontal_in_place();
+        grid
+    }
+
+    // Mirror top to bottom, the row order is reversed
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let mut grid = self.clone();
+        grid.flip_vertical_in_place();
+        grid
+    }
+
+    // Returns None without changing the grid unless it is square
+    pub fn transpose_in_place(&mut self) -> Option<()> {
+        if !self.is_square() {
+            return None;
+        }
+        let n = self.row_len;
+        for y in 0..n {
+            for x in (y + 1)..n {
+                self.data.swap(y * n + x, x * n + y);
+            }
+        }
+        Some(())
+    }
+
+    pub fn rotate_90_in_place(&mut self) -> Option<()> {
+        self.transpose_in_place()?;
+        self.flip_horizontal_in_place();
+        Some(())
+    }
+
+    pub fn rotate_270_in_place(&mut self) -> Option<()> {
+        self.transpose_in_place()?;
+        self.flip_vertical_in_place();
+        Some(())
+    }
+
+    pub fn rotate_180_in_place(&mut self) {
+        self.data.reverse();
+    }
+
+    pub fn flip_horizontal_in_place(&mut self) {
+        for row in self.data.chunks_mut(self.row_len.max(1)) {
+            row.reverse();
+        }
+    }
+
+    pub fn flip_vertical_in_place(&mut self) {
+        let row_len = self.row_len;
+        for y in 0..self.col_len / 2 {
+            let other = self.col_len - 1 - y;
+            for x in 0..row_len {
+                self.data.swap(y * row_len + x, other * row_len + x);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2 rows, 3 columns
+    fn make_wide_grid() -> Grid<i32> {
+        Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    fn make_square_grid() -> Grid<i32> {
+        Grid::from_vecs(&[vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]])
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = make_wide_grid().transpose();
+        assert_eq!(grid.data, vec![1, 4, 2, 5, 3, 6]);
+        assert_eq!(grid.row_len, 2);
+        assert_eq!(grid.col_len, 3);
+    }
+
+    #[test]
+    fn test_rotate_90() {
+        let grid = make_wide_grid().rotate_90();
+        assert_eq!(grid.data, vec![4, 1, 5, 2, 6, 3]);
+        assert_eq!(grid.row_len, 2);
+        assert_eq!(grid.col_len, 3);
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        let grid = make_wide_grid().rotate_180();
+        assert_eq!(grid.data, vec![6, 5, 4, 3, 2, 1]);
+        assert_eq!(grid.row_len, 3);
+        assert_eq!(grid.col_len, 2);
+    }
+
+    #[test]
+    fn test_rotate_270() {
+        let grid = make_wide_grid().rotate_270();
+        assert_eq!(grid.data, vec![3, 6, 2, 5, 1, 4]);
+        assert_eq!(grid.row_len, 2);
+        assert_eq!(grid.col_len, 3);
+    }
+
+    #[test]
+    fn test_rotations_compose() {
+        let grid = make_wide_grid();
+        assert_eq!(grid.rotate_90().rotate_90().data, grid.rotate_180().data);
+        assert_eq!(grid.rotate_90().rotate_270().data, grid.data);
+        let full_turn = grid.rotate_90().rotate_90().rotate_90().rotate_90();
+        assert_eq!(full_turn.data, grid.data);
+        assert_eq!(full_turn.row_len, grid.row_len);
+    }
+
+    #[test]
+    fn test_flips() {
+        let grid = make_wide_grid();
+        assert_eq!(grid.flip_horizontal().data, vec![3, 2, 1, 6, 5, 4]);
+        assert_eq!(grid.flip_vertical().data, vec![4, 5, 6, 1, 2, 3]);
+        assert_eq!(
+            grid.flip_horizontal().flip_vertical().data,
+            grid.rotate_180().data
+        );
+    }
+
+    #[test]
+    fn test_in_place_square() {
+        let grid = make_square_grid();
+
+        let mut rotated = grid.clone();
+        assert_eq!(rotated.rotate_90_in_place(), Some(()));
+        assert_eq!(rotated.data, grid.rotate_90().data);
+
+        let mut rotated = grid.clone();
+        assert_eq!(rotated.rotate_270_in_place(), Some(()));
+        assert_eq!(rotated.data, grid.rotate_270().data);
+
+        let mut transposed = grid.clone();
+        assert_eq!(transposed.transpose_in_place(), Some(()));
+        assert_eq!(transposed.data, grid.transpose().data);
+    }
+
+    #[test]
+    fn test_in_place_non_square() {
+        let mut grid = make_wide_grid();
+        assert_eq!(grid.transpose_in_place(), None);
+        assert_eq!(grid.rotate_90_in_place(), None);
+        assert_eq!(grid.data, vec![1, 2, 3, 4, 5, 6]);
+
+        grid.flip_vertical_in_place();
+        assert_eq!(grid.data, vec![4, 5, 6, 1, 2, 3]);
+        grid.flip_horizontal_in_place();
+        assert_eq!(grid.data, vec![6, 5, 4, 3, 2, 1]);
+    }
+}
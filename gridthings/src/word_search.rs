@@ -0,0 +1,159 @@
+use crate::{direction::DirectionSet, grid::Grid, segment::Segment};
+use std::collections::HashMap;
+
+// Prefix tree over the search words so every word sharing a prefix is checked in one walk
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: HashMap<char, usize>,
+    is_word: bool,
+}
+
+#[derive(Debug)]
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+impl Trie {
+    fn new(words: &[&str]) -> Self {
+        let mut nodes = vec![TrieNode::default()];
+        for word in words {
+            if word.is_empty() {
+                continue;
+            }
+            let mut node = 0;
+            for c in word.chars() {
+                node = match nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            nodes[node].is_word = true;
+        }
+        Self { nodes }
+    }
+}
+
+impl Grid<char> {
+    // Every occurrence of any of the words read in one of the directions, as Segments from the
+    // first letter to the last. Cells whose letter starts no word are skipped right away and
+    // each direction stops as soon as the letters read so far are not a prefix of any word.
+    // One letter words match once per cell, reported in the first of the directions
+    pub fn find_words<D: Into<DirectionSet>>(
+        &self,
+        words: &[&str],
+        directions: D,
+    ) -> Vec<Segment<char>> {
+        let trie = Trie::new(words);
+        let directions = directions.into();
+        let mut matches = Vec::new();
+
+        for start in self.iter_cells() {
+            let Some(&first) = trie.nodes[0].children.get(&start.value) else {
+                continue;
+            };
+            if let Some(direction) = directions.iter().next() {
+                if trie.nodes[first].is_word {
+                    matches.push(self.get_segment(start.y, start.x, direction, 1).unwrap());
+                }
+            }
+            for direction in directions.iter() {
+                let (y_step, x_step) = direction.delta();
+                let mut node = first;
+                let mut length = 1;
+                loop {
+                    let y = start.y + length as i32 * y_step;
+                    let x = start.x + length as i32 * x_step;
+                    let Some(value) = self.get_value(y, x) else {
+                        break;
+                    };
+                    let Some(&child) = trie.nodes[node].children.get(&value) else {
+                        break;
+                    };
+                    node = child;
+                    length += 1;
+                    if trie.nodes[node].is_word {
+                        let segment = self.get_segment(start.y, start.x, direction.clone(), length);
+                        matches.push(segment.unwrap());
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, direction::Direction};
+
+    fn make_word_grid() -> Grid<char> {
+        Grid::from_str("XMAS\nMMXA\nAXAS\nSXMX").unwrap()
+    }
+
+    #[test]
+    fn test_find_single_word() {
+        let grid = make_word_grid();
+        let matches = grid.find_words(&["XMAS"], DirectionSet::ALL);
+        assert_eq!(matches.len(), 2);
+
+        let across = &matches[0];
+        assert_eq!(across.direction, Direction::Right);
+        assert_eq!(across.start(), Some(&Cell::new('X', 0, 0)));
+        assert_eq!(across.end(), Some(&Cell::new('S', 0, 3)));
+
+        let down = &matches[1];
+        assert_eq!(down.direction, Direction::Down);
+        assert_eq!(down.values, vec!['X', 'M', 'A', 'S']);
+    }
+
+    #[test]
+    fn test_find_words_respects_directions() {
+        let grid = make_word_grid();
+        let matches = grid.find_words(&["XMAS"], Direction::Right);
+        assert_eq!(matches.len(), 1);
+        assert!(grid.find_words(&["XMAS"], Direction::Left).is_empty());
+    }
+
+    #[test]
+    fn test_find_many_words_with_shared_prefix() {
+        let grid = make_word_grid();
+        let words = ["XM", "XMA", "XMAS", "AS", "ZZZ", ""];
+        let matches = grid.find_words(&words, DirectionSet::CARDINAL);
+        let mut found: Vec<String> = matches
+            .iter()
+            .map(|segment| segment.values.iter().collect())
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                "AS", "AS", "AS", "AS", "AS", "XM", "XM", "XM", "XM", "XM", "XM", "XMA", "XMA",
+                "XMAS", "XMAS"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_one_letter_words_match_once_per_cell() {
+        let grid = make_word_grid();
+        let matches = grid.find_words(&["X", "XM"], DirectionSet::ALL);
+        let singles: Vec<&Segment<char>> = matches.iter().filter(|m| m.len() == 1).collect();
+        assert_eq!(singles.len(), 5);
+        assert!(singles.iter().all(|m| m.direction == Direction::Right));
+        assert_eq!(singles[0].start(), Some(&Cell::new('X', 0, 0)));
+        assert!(grid.find_words(&["X"], DirectionSet::EMPTY).is_empty());
+    }
+
+    #[test]
+    fn test_find_words_no_match() {
+        let grid = make_word_grid();
+        assert!(grid.find_words(&[], DirectionSet::ALL).is_empty());
+        assert!(grid.find_words(&["QQ"], DirectionSet::ALL).is_empty());
+    }
+}
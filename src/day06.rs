@@ -1,56 +1,47 @@
-use gridthings::{Cell, Direction, Grid};
+use gridthings::{Cell, Direction, Grid, MapParser, Point, Pose, RayStop};
 use log::debug;
 use rayon::prelude::*;
 use std::{collections::HashSet, fs::read_to_string};
 
-fn read(fname: &str) -> Grid<char> {
+// The map and the guard's starting point. The guard stays in the grid as '^' so part2
+// never tries to put an obstruction on it
+fn read(fname: &str) -> (Grid<char>, Point) {
     let content = read_to_string(fname).unwrap();
-    let data: Vec<Vec<char>> = content.lines().map(|line| line.chars().collect()).collect();
-    Grid::from_vecs(&data)
+    let (grid, markers) = MapParser::new(Some).marker('^').parse(&content).unwrap();
+    (grid, markers[&'^'][0])
 }
 
 pub fn part1(fname: &str) -> i32 {
-    let grid = read(fname);
-    let mut cell = grid.iter_cells().find(|c| c.value == '^').unwrap();
+    let (grid, start) = read(fname);
+    let mut cell = grid.get_cell_at(start).unwrap();
     debug!("start cell: {:?}", cell);
 
     let mut visited = HashSet::new();
-    // Skip to make Up the first direction
-    let mut directions = Direction::cardinal().into_iter().cycle().skip(3);
-    let mut direction = directions.next().unwrap();
+    visited.insert(cell.clone());
+    let mut direction = Direction::Up;
 
     loop {
-        visited.insert(cell.clone());
-        let next_cell = grid.get_cell_neighbor(cell.y, cell.x, direction.clone());
-        if next_cell.is_none() {
-            break;
-        }
-        let next_cell = next_cell.unwrap();
-        match next_cell.value {
-            '#' => direction = directions.next().unwrap(),
-            _ => cell = next_cell,
+        let (path, stop) = grid
+            .trace_until(cell.y, cell.x, direction.clone(), |c| c.value == '#')
+            .unwrap();
+        visited.extend(path);
+        match stop {
+            RayStop::Hit { last, .. } => {
+                cell = last;
+                direction = direction.turn_right();
+            }
+            RayStop::Exit { .. } => break,
         }
     }
     visited.len() as i32
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-struct Observation {
-    cell: Cell<char>,
-    direction: Direction,
-}
-
 fn grid_is_looped(grid: &Grid<char>, mut cell: Cell<char>) -> bool {
     let mut visited = HashSet::new();
-    // Skip to make Up the first direction
-    let mut directions = Direction::cardinal().into_iter().cycle().skip(3);
-    let mut direction = directions.next().unwrap();
+    let mut direction = Direction::Up;
 
     loop {
-        let observation = Observation {
-            cell: cell.clone(),
-            direction: direction.clone(),
-        };
+        let observation = Pose::new(cell.y, cell.x, direction.clone());
         if visited.contains(&observation) {
             return true;
         }
@@ -60,7 +51,7 @@ fn grid_is_looped(grid: &Grid<char>, mut cell: Cell<char>) -> bool {
         }
         let next_cell = next_cell.unwrap();
         match next_cell.value {
-            '#' => direction = directions.next().unwrap(),
+            '#' => direction = direction.turn_right(),
             _ => {
                 visited.insert(observation);
                 cell = next_cell;
@@ -71,8 +62,8 @@ fn grid_is_looped(grid: &Grid<char>, mut cell: Cell<char>) -> bool {
 }
 
 pub fn part2(fname: &str) -> i32 {
-    let grid = read(fname);
-    let start_cell = grid.iter_cells().find(|cell| cell.value == '^').unwrap();
+    let (grid, start) = read(fname);
+    let start_cell = grid.get_cell_at(start).unwrap();
     debug!("start_cell: {:?}", start_cell);
 
     grid.iter_cells()
@@ -11,39 +11,6 @@ fn read(fname: &str) -> Grid<u32> {
     Grid::from_vecs(&data)
 }
 
-fn dfs_unique_peaks(
-    grid: &Grid<u32>,
-    y: i32,
-    x: i32,
-    visited: &mut HashSet<(i32, i32)>,
-    peaks: &mut HashSet<(i32, i32)>,
-) {
-    let current_height = grid.get_value(y, x).unwrap();
-
-    // If we reached a peak, record it
-    if current_height == 9 {
-        peaks.insert((y, x));
-        return;
-    }
-
-    visited.insert((y, x));
-
-    // Try each cardinal direction
-    for neighbor in grid.get_cell_neighbors(y, x, Direction::cardinal()) {
-        // Skip if already visited
-        if visited.contains(&(neighbor.y, neighbor.x)) {
-            continue;
-        }
-
-        // Only follow path if height increases by exactly 1
-        if neighbor.value == current_height + 1 {
-            dfs_unique_peaks(grid, neighbor.y, neighbor.x, visited, peaks);
-        }
-    }
-
-    visited.remove(&(y, x));
-}
-
 fn dfs_count_paths(grid: &Grid<u32>, y: i32, x: i32, visited: &mut HashSet<(i32, i32)>) -> i32 {
     let current_height = grid.get_value(y, x).unwrap();
 
@@ -56,7 +23,7 @@ fn dfs_count_paths(grid: &Grid<u32>, y: i32, x: i32, visited: &mut HashSet<(i32,
 
     let mut path_count = 0;
     // Try each cardinal direction
-    for neighbor in grid.get_cell_neighbors(y, x, Direction::cardinal()) {
+    for neighbor in grid.neighbors(y, x, &Direction::CARDINAL) {
         // Skip if already visited
         if visited.contains(&(neighbor.y, neighbor.x)) {
             continue;
@@ -73,10 +40,16 @@ fn dfs_count_paths(grid: &Grid<u32>, y: i32, x: i32, visited: &mut HashSet<(i32,
 }
 
 fn find_reachable_peaks(grid: &Grid<u32>, start_y: i32, start_x: i32) -> HashSet<(i32, i32)> {
-    let mut peaks = HashSet::new();
-    let mut visited = HashSet::new();
-    dfs_unique_peaks(grid, start_y, start_x, &mut visited, &mut peaks);
-    peaks
+    // Only follow path if height increases by exactly 1
+    let result = grid.bfs(start_y, start_x, Direction::cardinal(), |from, to| {
+        to.value == from.value + 1
+    });
+    result
+        .distances
+        .keys()
+        .filter(|(y, x)| grid.get_value(*y, *x) == Some(9))
+        .copied()
+        .collect()
 }
 
 fn count_unique_paths(grid: &Grid<u32>, start_y: i32, start_x: i32) -> i32 {